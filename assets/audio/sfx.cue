@@ -0,0 +1,16 @@
+# name | sound | volume | volume variation | pitch variation | max instances | cooldown
+#
+# Sounds are paths inside the assets folder, either recordings or `.sfx` synth presets. Each
+# time a cue plays, its volume and pitch are randomly varied by up to the given fraction, e.g.
+# 0.1 means +-10%. Once the cue is playing `max instances` times, its oldest instance is cut
+# off. The cue doesn't play again until `cooldown` seconds after it last played.
+
+click        | audio/sound_effects/click.sfx              | 0.1 | 0.1  | 0.1  | 4 | 0.03
+button_click | audio/sound_effects/button_click.ogg       | 0.4 | 0.05 | 0.08 | 3 | 0.05
+break        | audio/sound_effects/break.sfx              | 0.2 | 0.1  | 0.15 | 4 | 0.03
+guide_blip   | audio/sound_effects/new_text.sfx           | 0.1 | 0.1  | 0.05 | 2 | 0.05
+achievement  | audio/sound_effects/new_text.sfx           | 0.3 | 0    | 0    | 2 | 0
+ui_hover     | audio/sound_effects/ui/button_hover.ogg    | 1.0 | 0.05 | 0.05 | 2 | 0.03
+ui_click     | audio/sound_effects/ui/button_click.ogg    | 1.0 | 0.05 | 0.05 | 2 | 0.03
+threat_spawn | audio/sound_effects/synth/threat_spawn.sfx | 0.6 | 0.1  | 0.1  | 3 | 0.1
+threat_near  | audio/sound_effects/synth/threat_near.sfx  | 0.5 | 0.05 | 0.05 | 3 | 0.1
@@ -0,0 +1,152 @@
+//! The beat of the music. The soundtrack inserts a [`BeatClock`] with its [`TempoMap`] for each
+//! run, and the clock sends [`BeatEvent`]s as the music goes on.
+
+use bevy::prelude::*;
+
+use crate::audio::AudioPause;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<BeatEvent>()
+        .add_systems(Update, tick_beat_clock.run_if(resource_exists::<BeatClock>));
+}
+
+/// Sent on every beat of the music. Bars are found with [`BeatClock::next_bar`].
+#[derive(Event, Debug, Clone, Copy)]
+pub struct BeatEvent;
+
+/// When the beats of a piece of music are, in seconds from its start.
+#[derive(Clone, Debug)]
+pub enum TempoMap {
+    /// A steady tempo, with the first beat `offset` seconds in.
+    Constant {
+        bpm: f32,
+        offset: f32,
+        beats_per_bar: u32,
+    },
+    /// The time of each beat, for music with a changing tempo. Beats after the last one follow
+    /// the tempo of the last two.
+    Timestamps { beats: Vec<f32>, beats_per_bar: u32 },
+}
+
+impl Default for TempoMap {
+    /// Music without a tempo map counts 120 BPM in 4/4.
+    fn default() -> Self {
+        Self::Constant {
+            bpm: 120.0,
+            offset: 0.0,
+            beats_per_bar: 4,
+        }
+    }
+}
+
+impl TempoMap {
+    pub fn beats_per_bar(&self) -> u32 {
+        match self {
+            Self::Constant { beats_per_bar, .. } | Self::Timestamps { beats_per_bar, .. } => {
+                *beats_per_bar
+            }
+        }
+    }
+
+    /// Beats since the first beat at the given time, negative before it.
+    pub fn position(&self, time: f32) -> f32 {
+        match self {
+            Self::Constant { bpm, offset, .. } => (time - offset) * bpm / 60.0,
+            Self::Timestamps { beats, .. } => {
+                // index of the beat starting the interval the time falls into
+                let index = beats
+                    .partition_point(|beat| *beat <= time)
+                    .clamp(1, beats.len() - 1)
+                    - 1;
+                let (start, end) = (beats[index], beats[index + 1]);
+                index as f32 + (time - start) / (end - start)
+            }
+        }
+    }
+
+    /// Time of the given beat position, the inverse of [`TempoMap::position`].
+    pub fn time(&self, position: f32) -> f32 {
+        match self {
+            Self::Constant { bpm, offset, .. } => offset + position * 60.0 / bpm,
+            Self::Timestamps { beats, .. } => {
+                let index = (position.floor().max(0.0) as usize).min(beats.len() - 2);
+                let (start, end) = (beats[index], beats[index + 1]);
+                start + (position - index as f32) * (end - start)
+            }
+        }
+    }
+}
+
+/// Seconds the music has played, on its [`TempoMap`]. It stops while the music is paused.
+#[derive(Resource, Debug)]
+pub struct BeatClock {
+    elapsed: f32,
+    tempo: TempoMap,
+}
+
+impl BeatClock {
+    pub fn new(tempo: TempoMap) -> Self {
+        Self {
+            elapsed: 0.0,
+            tempo,
+        }
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn beats_per_bar(&self) -> u32 {
+        self.tempo.beats_per_bar()
+    }
+
+    /// Beats since the first beat, e.g. 4.5 halfway through the fifth beat.
+    pub fn position(&self) -> f32 {
+        self.tempo.position(self.elapsed)
+    }
+
+    /// Seconds the current beat lasts.
+    pub fn beat_duration(&self) -> f32 {
+        let beat = self.position().floor();
+        self.tempo.time(beat + 1.0) - self.tempo.time(beat)
+    }
+
+    /// Time of the given beat position.
+    pub fn time_of(&self, position: f32) -> f32 {
+        self.tempo.time(position)
+    }
+
+    /// Time of the next beat, or now if the music is exactly on the beat.
+    pub fn next_beat(&self) -> f32 {
+        self.time_of(self.position().ceil())
+    }
+
+    /// Time of the first beat of the next bar, or now if the music is exactly on it.
+    pub fn next_bar(&self) -> f32 {
+        let beats_per_bar = self.beats_per_bar() as f32;
+        self.time_of((self.position() / beats_per_bar).ceil() * beats_per_bar)
+    }
+}
+
+pub fn tick_beat_clock(
+    mut clock: ResMut<BeatClock>,
+    audio_pause: Res<AudioPause>,
+    mut beat_events: EventWriter<BeatEvent>,
+    time: Res<Time<Real>>,
+) {
+    // The clock stops with the paused music, so it stays in sync with it.
+    if audio_pause.is_paused() {
+        return;
+    }
+
+    let before = clock.position();
+    clock.elapsed += time.delta_secs();
+    let after = clock.position();
+
+    // every beat in [before, after), there can be several after a long frame
+    let first = before.ceil().max(0.0) as u32;
+    let end = after.ceil().max(0.0) as u32;
+    for _ in first..end {
+        beat_events.write(BeatEvent);
+    }
+}
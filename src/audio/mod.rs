@@ -0,0 +1,277 @@
+pub mod beat_clock;
+pub mod sfx;
+pub mod spatial;
+pub mod stem;
+pub mod synth;
+
+use bevy::{
+    audio::{AudioSinkPlayback, Decodable, Volume},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{Pause, menus::Menu};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((
+        beat_clock::plugin,
+        sfx::plugin,
+        spatial::plugin,
+        stem::plugin,
+        synth::plugin,
+    ));
+
+    app.register_type::<Music>();
+    app.register_type::<SoundEffect>();
+    app.register_type::<Gain>();
+    app.register_type::<PausableAudio>();
+    app.init_resource::<CategoryVolumes>();
+    app.init_resource::<AudioPause>();
+
+    app.add_systems(PreUpdate, pause_audio);
+    app.add_systems(PostUpdate, apply_volumes);
+}
+
+/// Real-time seconds of the fade before [`PausableAudio`] pauses, and after it resumes.
+const PAUSE_FADE: f32 = 0.25;
+
+/// Volume of each audio category, on top of the [`GlobalVolume`].
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct CategoryVolumes {
+    pub music: f32,
+    pub sound_effects: f32,
+}
+
+impl Default for CategoryVolumes {
+    fn default() -> Self {
+        Self {
+            music: 1.0,
+            sound_effects: 1.0,
+        }
+    }
+}
+
+/// Volume automation of a playing sound, e.g. for fades. Multiplies the volume of its
+/// [`PlaybackSettings`].
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Debug)]
+#[reflect(Component)]
+pub struct Gain(pub f32);
+
+impl Default for Gain {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
+/// general "music" category (e.g. global background music, soundtrack).
+///
+/// This can then be used to query for and operate on sounds in that category.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Music;
+
+/// A music audio instance.
+pub fn music(handle: Handle<AudioSource>, volume: f32) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::LOOP.with_volume(Volume::Linear(volume)),
+        Music,
+    )
+}
+
+/// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
+/// general "sound effect" category (e.g. footsteps, the sound of a magic spell, a door opening).
+///
+/// This can then be used to query for and operate on sounds in that category.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct SoundEffect;
+
+/// A sound effect audio instance, of a recording or a [`Synth`](synth::Synth). Sound effects are
+/// usually played as cues with [`PlaySfx`](sfx::PlaySfx) instead.
+pub fn sound_effect<S: Asset + Decodable>(
+    handle: Handle<S>,
+    volume: f32,
+    speed: f32,
+) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN
+            .with_volume(Volume::Linear(volume))
+            .with_speed(speed),
+        SoundEffect,
+    )
+}
+
+/// A sound effect panned by its position relative to the [`spatial::listener`]. It needs a
+/// [`Transform`], and is usually spawned as a child of the entity making the sound.
+pub fn spatial_sound_effect<S: Asset + Decodable>(
+    handle: Handle<S>,
+    volume: f32,
+    speed: f32,
+) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings {
+            spatial: true,
+            spatial_scale: Some(spatial::spatial_scale()),
+            ..PlaybackSettings::DESPAWN
+                .with_volume(Volume::Linear(volume))
+                .with_speed(speed)
+        },
+        Transform::default(),
+        Gain::default(),
+        SoundEffect,
+    )
+}
+
+/// An organizational marker component for audio which pauses with the game, like the soundtrack
+/// and sound effects of the run. Sound effects played while no menu is open get it.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct PausableAudio;
+
+/// Whether [`PausableAudio`] is paused. It fades out and pauses while a menu is open over the
+/// paused game or the window isn't focused, and resumes where it left off.
+#[derive(Resource, Debug)]
+pub struct AudioPause {
+    /// Volume of the fade, 0 once the audio is paused.
+    level: f32,
+    paused: bool,
+    menu_open: bool,
+}
+
+impl Default for AudioPause {
+    fn default() -> Self {
+        Self {
+            level: 1.0,
+            paused: false,
+            menu_open: false,
+        }
+    }
+}
+
+impl AudioPause {
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Whether a menu is open over the paused game. Sounds played from it keep playing.
+    pub fn is_menu_open(&self) -> bool {
+        self.menu_open
+    }
+}
+
+/// Marks sinks paused by [`AudioPause`], so only they are resumed.
+#[derive(Component)]
+struct PausedWithGame;
+
+/// The sink of a playing sound, spatial sounds have a [`SpatialAudioSink`] instead of an
+/// [`AudioSink`].
+fn playing_sink<'a>(
+    sinks: (Option<&'a AudioSink>, Option<&'a SpatialAudioSink>),
+) -> &'a dyn AudioSinkPlayback {
+    match sinks {
+        (Some(sink), _) => sink,
+        (_, Some(sink)) => sink,
+        (None, None) => unreachable!("`AnyOf` matches at least one sink"),
+    }
+}
+
+fn pause_audio(
+    mut commands: Commands,
+    mut audio_pause: ResMut<AudioPause>,
+    pause: Res<State<Pause>>,
+    menu: Res<State<Menu>>,
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    sinks: Query<
+        (
+            Entity,
+            AnyOf<(&AudioSink, &SpatialAudioSink)>,
+            Has<PausedWithGame>,
+        ),
+        With<PausableAudio>,
+    >,
+    time: Res<Time<Real>>,
+) {
+    audio_pause.menu_open = pause.get().0 && *menu.get() != Menu::None;
+    let unfocused = window.is_some_and(|window| !window.focused);
+    let step = time.delta_secs() / PAUSE_FADE;
+
+    if audio_pause.menu_open || unfocused {
+        audio_pause.level = (audio_pause.level - step).max(0.0);
+        if audio_pause.level > 0.0 {
+            return;
+        }
+        audio_pause.paused = true;
+        // Also catches sounds which started after the others were paused.
+        for (entity, sink, paused_with_game) in &sinks {
+            let sink = playing_sink(sink);
+            if !paused_with_game && !sink.is_paused() {
+                sink.pause();
+                commands.entity(entity).insert(PausedWithGame);
+            }
+        }
+        return;
+    }
+
+    if audio_pause.paused {
+        audio_pause.paused = false;
+        // All sinks resume in the same frame, so the soundtrack layers stay in sync.
+        for (entity, sink, paused_with_game) in &sinks {
+            if paused_with_game {
+                playing_sink(sink).play();
+                commands.entity(entity).remove::<PausedWithGame>();
+            }
+        }
+    }
+    audio_pause.level = (audio_pause.level + step).min(1.0);
+}
+
+/// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system updates them
+/// together with their category volume, [`Gain`] and pause fade.
+pub fn apply_volumes(
+    global_volume: Res<GlobalVolume>,
+    category_volumes: Res<CategoryVolumes>,
+    audio_pause: Res<AudioPause>,
+    mut audio_query: Query<(
+        &PlaybackSettings,
+        AnyOf<(&mut AudioSink, &mut SpatialAudioSink)>,
+        Option<&Gain>,
+        Has<Music>,
+        Has<SoundEffect>,
+        Has<PausableAudio>,
+    )>,
+) {
+    for (playback, sinks, gain, is_music, is_sound_effect, is_pausable) in &mut audio_query {
+        let sink: &mut dyn AudioSinkPlayback = match sinks {
+            (Some(sink), _) => sink.into_inner(),
+            (_, Some(sink)) => sink.into_inner(),
+            (None, None) => unreachable!("`AnyOf` matches at least one sink"),
+        };
+        let category = match (is_music, is_sound_effect) {
+            (true, _) => category_volumes.music,
+            (_, true) => category_volumes.sound_effects,
+            _ => 1.0,
+        };
+        let mut gain = gain.map_or(1.0, |gain| gain.0);
+        if is_pausable {
+            gain *= audio_pause.level;
+        }
+        let volume = global_volume.volume * playback.volume * Volume::Linear(category * gain);
+        if sink.volume() != volume {
+            sink.set_volume(volume);
+        }
+    }
+}
+
+/// White noise in -1..1, the same for the same sample. Used by the [`stem`]s and [`synth`]s
+/// rendered at load time, so they always give the same samples.
+fn noise(sample: u64) -> f32 {
+    let mut x = sample.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    x ^= x >> 31;
+    x = x.wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x ^= x >> 29;
+    (x >> 40) as f32 / (1u64 << 23) as f32 - 1.0
+}
@@ -0,0 +1,235 @@
+//! Sound effects, played as named cues, e.g. `commands.trigger(PlaySfx("button_click"))`.
+//! The cues are defined in `assets/audio/sfx.cue` and all of their sounds are loaded up front.
+//!
+//! Cues triggered for an entity are played at its position, see [`spatial`](super::spatial).
+//! Their sounds are recordings or [`synth`](super::synth) presets.
+
+use std::collections::{HashMap, VecDeque};
+
+use bevy::{
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    audio::Decodable,
+    ecs::system::EntityCommands,
+    prelude::*,
+};
+use itertools::Itertools;
+use rand::Rng;
+use thiserror::Error;
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::{
+        AudioPause, PausableAudio, SoundEffect, sound_effect, spatial_sound_effect, synth::Synth,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<SfxLibrary>()
+        .init_asset_loader::<SfxLibraryLoader>()
+        .load_resource::<SfxAssets>()
+        .init_resource::<SfxVoices>()
+        .add_observer(play_sfx);
+}
+
+/// Plays the sound effect cue with the given name, at the target entity if there is one.
+#[derive(Event)]
+pub struct PlaySfx(pub &'static str);
+
+enum CueSound {
+    Recording(Handle<AudioSource>),
+    /// A `.sfx` preset.
+    Synth(Handle<Synth>),
+}
+
+struct SfxCue {
+    sound: CueSound,
+    volume: f32,
+    /// Fraction by which the volume randomly varies each time the cue plays.
+    volume_variation: f32,
+    /// Fraction by which the playback speed, and so the pitch, randomly varies.
+    pitch_variation: f32,
+    /// Instances playing at the same time, the oldest one is cut off by a new one.
+    max_instances: usize,
+    /// Real-time seconds before the cue can play again.
+    cooldown: f32,
+}
+
+/// Sound effect cues by name, loaded from a `.cue` file.
+#[derive(Asset, TypePath)]
+struct SfxLibrary(HashMap<String, SfxCue>);
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct SfxAssets {
+    #[dependency]
+    library: Handle<SfxLibrary>,
+}
+
+impl FromWorld for SfxAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            library: assets.load("audio/sfx.cue"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SfxLibraryLoader;
+
+#[derive(Error, Debug)]
+enum SfxLibraryLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(
+        "Line {0}: expected `name | sound | volume | volume variation | pitch variation | max instances | cooldown`."
+    )]
+    InvalidFormat(usize),
+    #[error("Line {0}: `{1}` is not a valid number.")]
+    InvalidNumber(usize, String),
+    #[error("Line {0}: cue `{1}` is already defined.")]
+    DuplicateCue(usize, String),
+}
+
+impl AssetLoader for SfxLibraryLoader {
+    type Asset = SfxLibrary;
+    type Settings = ();
+    type Error = SfxLibraryLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+
+        let mut cues = HashMap::new();
+        for (line, l) in text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !(l.is_empty() || l.starts_with("#")))
+        {
+            let (name, sound, volume, volume_variation, pitch_variation, max_instances, cooldown) =
+                l.split('|')
+                    .map(|t| t.trim())
+                    .collect_tuple()
+                    .ok_or(SfxLibraryLoaderError::InvalidFormat(line))?;
+            let number = |text: &str| {
+                text.parse::<f32>()
+                    .ok()
+                    .filter(|number| *number >= 0.0)
+                    .ok_or_else(|| SfxLibraryLoaderError::InvalidNumber(line, text.into()))
+            };
+
+            let cue = SfxCue {
+                sound: if sound.ends_with(".sfx") {
+                    CueSound::Synth(load_context.load(sound))
+                } else {
+                    CueSound::Recording(load_context.load(sound))
+                },
+                volume: number(volume)?,
+                volume_variation: number(volume_variation)?,
+                pitch_variation: number(pitch_variation)?,
+                max_instances: max_instances
+                    .parse()
+                    .ok()
+                    .filter(|max_instances| *max_instances > 0)
+                    .ok_or_else(|| {
+                        SfxLibraryLoaderError::InvalidNumber(line, max_instances.into())
+                    })?,
+                cooldown: number(cooldown)?,
+            };
+            if cues.insert(name.to_string(), cue).is_some() {
+                return Err(SfxLibraryLoaderError::DuplicateCue(line, name.into()));
+            }
+        }
+
+        Ok(SfxLibrary(cues))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["cue"]
+    }
+}
+
+/// Playing instances of each cue, oldest first, and when the cue last played.
+#[derive(Resource, Default)]
+struct SfxVoices(HashMap<&'static str, (VecDeque<Entity>, f32)>);
+
+fn play_sfx(
+    trigger: Trigger<PlaySfx>,
+    mut commands: Commands,
+    sfx_assets: Option<Res<SfxAssets>>,
+    libraries: Res<Assets<SfxLibrary>>,
+    mut voices: ResMut<SfxVoices>,
+    playing: Query<(), With<SoundEffect>>,
+    emitters: Query<(), With<GlobalTransform>>,
+    audio_pause: Res<AudioPause>,
+    time: Res<Time<Real>>,
+) {
+    let name = trigger.event().0;
+    let Some(library) = sfx_assets.and_then(|sfx_assets| libraries.get(&sfx_assets.library)) else {
+        return;
+    };
+    let Some(cue) = library.0.get(name) else {
+        warn!("Unknown sound effect cue `{name}`.");
+        return;
+    };
+
+    let emitter = Some(trigger.target()).filter(|target| *target != Entity::PLACEHOLDER);
+    // The emitter is already gone.
+    if emitter.is_some_and(|emitter| !emitters.contains(emitter)) {
+        return;
+    }
+
+    let now = time.elapsed_secs();
+    let (instances, last_played) = voices.0.entry(name).or_insert((VecDeque::new(), f32::MIN));
+    if now - *last_played < cue.cooldown {
+        return;
+    }
+    *last_played = now;
+
+    // Finished instances despawn themselves.
+    instances.retain(|instance| playing.contains(*instance));
+    while instances.len() >= cue.max_instances {
+        let oldest = instances.pop_front().unwrap();
+        commands.entity(oldest).despawn();
+    }
+
+    let mut rng = rand::rng();
+    let volume = cue.volume * (1.0 + rng.random_range(-1.0..=1.0) * cue.volume_variation);
+    let speed = 1.0 + rng.random_range(-1.0..=1.0) * cue.pitch_variation;
+    let mut instance = commands.spawn(Name::new(format!("Sound effect {name}")));
+    match &cue.sound {
+        CueSound::Recording(sound) => {
+            insert_sound(&mut instance, sound.clone(), volume, speed, emitter)
+        }
+        CueSound::Synth(sound) => {
+            insert_sound(&mut instance, sound.clone(), volume, speed, emitter)
+        }
+    }
+    // Sounds of menus opened over the paused game keep playing.
+    if !audio_pause.is_menu_open() {
+        instance.insert(PausableAudio);
+    }
+    instances.push_back(instance.id());
+}
+
+/// Inserts the sound of a cue, as a child of its emitter if it has one.
+fn insert_sound<S: Asset + Decodable>(
+    instance: &mut EntityCommands,
+    sound: Handle<S>,
+    volume: f32,
+    speed: f32,
+    emitter: Option<Entity>,
+) {
+    match emitter {
+        Some(emitter) => {
+            instance.insert((spatial_sound_effect(sound, volume, speed), ChildOf(emitter)))
+        }
+        None => instance.insert(sound_effect(sound, volume, speed)),
+    };
+}
@@ -0,0 +1,112 @@
+//! Sounds of threats. They are panned by their horizontal position relative to the camera,
+//! which carries the [`SpatialListener`], and get louder the closer the threat is to its target.
+//!
+//! Cues are played at an entity with `commands.trigger_targets(PlaySfx("threat_spawn"), entity)`.
+
+use bevy::{audio::SpatialScale, prelude::*};
+
+use crate::{
+    PausableSystems,
+    audio::{Gain, SoundEffect, sfx::PlaySfx},
+    playfield::PLAYFIELD_SIZE,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            warn_on_arrival.in_set(PausableSystems),
+            attenuate_by_distance,
+        ),
+    );
+}
+
+/// Gain of sounds at [`Proximity::range`] from the target or further.
+const FAR_GAIN: f32 = 0.3;
+
+/// The listener for spatial sounds, sounds at the edges of the playfield are only heard in one ear.
+pub fn listener() -> SpatialListener {
+    SpatialListener::new(PLAYFIELD_SIZE.x)
+}
+
+/// Scales positions so that the ears of the [`listener`] are 1 apart from the center, as
+/// sounds closer to an ear than that aren't attenuated by their distance to it.
+pub fn spatial_scale() -> SpatialScale {
+    SpatialScale::new_2d(2.0 / PLAYFIELD_SIZE.x)
+}
+
+/// Makes sounds played at the entity louder the closer it is to its target.
+#[derive(Component, Debug)]
+pub struct Proximity {
+    pub target: Entity,
+    /// Distance from the target at which the sounds are at their quietest.
+    pub range: f32,
+}
+
+/// Plays a cue at the entity once it gets within `distance` of its [`Proximity`] target.
+#[derive(Component, Debug)]
+pub struct ArrivalWarning {
+    pub cue: &'static str,
+    pub distance: f32,
+    played: bool,
+}
+
+impl ArrivalWarning {
+    pub fn new(cue: &'static str, distance: f32) -> Self {
+        Self {
+            cue,
+            distance,
+            played: false,
+        }
+    }
+}
+
+/// Distance between the entity and its target, if the target still exists.
+fn target_distance(
+    transform: &GlobalTransform,
+    proximity: &Proximity,
+    targets: &Query<&GlobalTransform>,
+) -> Option<f32> {
+    let target = targets.get(proximity.target).ok()?;
+    Some(
+        transform
+            .translation()
+            .xy()
+            .distance(target.translation().xy()),
+    )
+}
+
+fn warn_on_arrival(
+    mut commands: Commands,
+    mut emitters: Query<(Entity, &GlobalTransform, &Proximity, &mut ArrivalWarning)>,
+    targets: Query<&GlobalTransform>,
+) {
+    for (entity, transform, proximity, mut warning) in &mut emitters {
+        let Some(distance) = target_distance(transform, proximity, &targets) else {
+            continue;
+        };
+        if !warning.played && distance <= warning.distance {
+            warning.played = true;
+            commands.trigger_targets(PlaySfx(warning.cue), entity);
+        }
+    }
+}
+
+fn attenuate_by_distance(
+    emitters: Query<(&GlobalTransform, &Proximity, &Children)>,
+    targets: Query<&GlobalTransform>,
+    mut sounds: Query<&mut Gain, With<SoundEffect>>,
+) {
+    for (transform, proximity, children) in &emitters {
+        let Some(distance) = target_distance(transform, proximity, &targets) else {
+            continue;
+        };
+        let closeness = 1.0 - (distance / proximity.range).min(1.0);
+        let gain = FAR_GAIN.lerp(1.0, closeness);
+
+        let mut sounds = sounds.iter_many_mut(children);
+        while let Some(mut sound_gain) = sounds.fetch_next() {
+            sound_gain.set_if_neq(Gain(gain));
+        }
+    }
+}
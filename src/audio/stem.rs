@@ -0,0 +1,265 @@
+//! Procedural music stems: drum and bass patterns rendered on the fly from `.stem` files.
+//! They are played like any other audio, with `AudioPlayer::<Stem>`.
+//!
+//! ```text
+//! tempo | 88 | 4
+//! kick  | 0.8 | x . . . . . x . x . . . . . . .
+//! bass  | 0.5 | C2 . . C2 . . C2 . G1 . . G1 . . . .
+//! ```
+//!
+//! The tempo line gives beats per minute and steps per beat. Each track is an instrument, its
+//! volume and a pattern of steps which repeats, `.` is a rest. Drums play on any other step,
+//! the bass plays the given note.
+
+use std::{f32::consts::TAU, sync::Arc, time::Duration};
+
+use bevy::{
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    audio::{AddAudioSource, Decodable, Source},
+    prelude::*,
+};
+use itertools::Itertools;
+use strum_macros::EnumString;
+use thiserror::Error;
+
+use crate::audio::noise;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_audio_source::<Stem>()
+        .init_asset_loader::<StemLoader>();
+}
+
+const SAMPLE_RATE: u32 = 44_100;
+/// Seconds after which a hit is silent, so older steps don't have to be looked at.
+const RING_TIME: f32 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[strum(ascii_case_insensitive)]
+enum Instrument {
+    Kick,
+    Snare,
+    Hat,
+    Bass,
+}
+
+struct Track {
+    instrument: Instrument,
+    volume: f32,
+    /// Frequency of each step, `None` for rests. Drums ignore the frequency.
+    steps: Vec<Option<f32>>,
+}
+
+#[derive(Asset, TypePath, Clone)]
+pub struct Stem {
+    /// Seconds per step.
+    step_duration: f32,
+    tracks: Arc<Vec<Track>>,
+}
+
+impl Decodable for Stem {
+    type DecoderItem = f32;
+    type Decoder = StemDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        StemDecoder {
+            stem: self.clone(),
+            sample: 0,
+        }
+    }
+}
+
+/// Renders a [`Stem`] forever, it is looped by its patterns.
+pub struct StemDecoder {
+    stem: Stem,
+    sample: u64,
+}
+
+impl Iterator for StemDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let time = self.sample as f32 / SAMPLE_RATE as f32;
+        // Times are taken relative to the current step, so precision doesn't degrade over time.
+        let step = (self.sample as f64 / SAMPLE_RATE as f64 / self.stem.step_duration as f64)
+            .floor() as usize;
+        let into_step = time - step as f32 * self.stem.step_duration;
+        let ring_steps = (RING_TIME / self.stem.step_duration).ceil() as usize;
+
+        let mut output = 0.0;
+        for track in self.stem.tracks.iter() {
+            // Tracks are monophonic, only their last hit sounds.
+            let last_hit = (0..=ring_steps.min(step)).find_map(|back| {
+                track.steps[(step - back) % track.steps.len()].map(|frequency| (back, frequency))
+            });
+            if let Some((back, frequency)) = last_hit {
+                let since_hit = into_step + back as f32 * self.stem.step_duration;
+                output += track.volume * play(track.instrument, since_hit, frequency, self.sample);
+            }
+        }
+
+        self.sample += 1;
+        Some(output.clamp(-1.0, 1.0))
+    }
+}
+
+impl Source for StemDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// One sample of an instrument `time` seconds after it was hit.
+fn play(instrument: Instrument, time: f32, frequency: f32, sample: u64) -> f32 {
+    match instrument {
+        Instrument::Kick => {
+            // pitch drops quickly from 150 Hz to 50 Hz
+            let phase = 50.0 * time + 100.0 * 0.03 * (1.0 - (-time / 0.03).exp());
+            (phase * TAU).sin() * (-time / 0.15).exp()
+        }
+        Instrument::Snare => {
+            let tone = (180.0 * time * TAU).sin() * (-time / 0.08).exp();
+            0.6 * noise(sample) * (-time / 0.06).exp() + 0.4 * tone
+        }
+        Instrument::Hat => 0.5 * noise(sample) * (-time / 0.02).exp(),
+        Instrument::Bass => {
+            let phase = frequency * time * TAU;
+            let wave = phase.sin() + 0.5 * (2.0 * phase).sin() + 0.25 * (3.0 * phase).sin();
+            let attack = (time / 0.005).min(1.0);
+            0.5 * wave * attack * (-time / 0.25).exp()
+        }
+    }
+}
+
+/// Frequency of a note such as `C2` or `F#1`.
+fn note_frequency(note: &str) -> Option<f32> {
+    let split = note.find(|char: char| char.is_ascii_digit() || char == '-')?;
+    let (name, octave) = note.split_at(split);
+    let semitone = match name.to_ascii_uppercase().as_str() {
+        "C" => 0,
+        "C#" | "DB" => 1,
+        "D" => 2,
+        "D#" | "EB" => 3,
+        "E" => 4,
+        "F" => 5,
+        "F#" | "GB" => 6,
+        "G" => 7,
+        "G#" | "AB" => 8,
+        "A" => 9,
+        "A#" | "BB" => 10,
+        "B" => 11,
+        _ => return None,
+    };
+    let octave: i32 = octave.parse().ok()?;
+    // MIDI note numbers, A4 = 69 = 440 Hz
+    let midi = (octave + 1) * 12 + semitone;
+    Some(440.0 * 2f32.powf((midi - 69) as f32 / 12.0))
+}
+
+#[derive(Default)]
+struct StemLoader;
+
+#[derive(Error, Debug)]
+enum StemLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {0}: expected `tempo | bpm | steps per beat` or `instrument | volume | steps`.")]
+    InvalidFormat(usize),
+    #[error("Line {0}: `{1}` is not a valid number.")]
+    InvalidNumber(usize, String),
+    #[error("Line {0}: unknown instrument `{1}`.")]
+    InvalidInstrument(usize, String),
+    #[error("Line {0}: `{1}` is not a note, e.g. `C2` or `F#1`.")]
+    InvalidNote(usize, String),
+    #[error("The stem needs a tempo line before its tracks.")]
+    MissingTempo,
+}
+
+impl AssetLoader for StemLoader {
+    type Asset = Stem;
+    type Settings = ();
+    type Error = StemLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+
+        let mut step_duration = None;
+        let mut tracks = Vec::new();
+        for (line, l) in text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !(l.is_empty() || l.starts_with("#")))
+        {
+            let (name, first, second) = l
+                .split('|')
+                .map(|t| t.trim())
+                .collect_tuple()
+                .ok_or(StemLoaderError::InvalidFormat(line))?;
+            let number = |text: &str| {
+                text.parse::<f32>()
+                    .ok()
+                    .filter(|number| *number > 0.0)
+                    .ok_or_else(|| StemLoaderError::InvalidNumber(line, text.into()))
+            };
+
+            if name.eq_ignore_ascii_case("tempo") {
+                step_duration = Some(60.0 / number(first)? / number(second)?);
+                continue;
+            }
+            if step_duration.is_none() {
+                return Err(StemLoaderError::MissingTempo);
+            }
+
+            let instrument: Instrument = name
+                .parse()
+                .map_err(|_| StemLoaderError::InvalidInstrument(line, name.into()))?;
+            let steps = second
+                .split_whitespace()
+                .map(|step| match step {
+                    "." => Ok(None),
+                    _ if instrument != Instrument::Bass => Ok(Some(0.0)),
+                    _ => note_frequency(step)
+                        .map(Some)
+                        .ok_or_else(|| StemLoaderError::InvalidNote(line, step.into())),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if steps.is_empty() {
+                return Err(StemLoaderError::InvalidFormat(line));
+            }
+
+            tracks.push(Track {
+                instrument,
+                volume: number(first)?,
+                steps,
+            });
+        }
+
+        Ok(Stem {
+            step_duration: step_duration.ok_or(StemLoaderError::MissingTempo)?,
+            tracks: Arc::new(tracks),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["stem"]
+    }
+}
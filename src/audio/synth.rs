@@ -0,0 +1,375 @@
+//! Procedural sound effects in the style of Bfxr, rendered from `.sfx` presets when they load.
+//! Sound effect cues can use a preset as their sound, like any recording.
+//!
+//! ```text
+//! wave     | square | 0.3
+//! envelope | 0.01 | 0.05 | 0.2
+//! pitch    | 880 | 440
+//! vibrato  | 0.5 | 12
+//! noise    | 0.1
+//! volume   | 0.6
+//! ```
+//!
+//! - `wave`: `sine`, `square`, `saw`, `triangle` or `noise`, squares can be given their duty cycle.
+//! - `envelope`: attack, sustain and decay in seconds, together they are the length of the sound.
+//! - `pitch`: the starting frequency in Hz, optionally sliding to the end frequency.
+//! - `vibrato`: depth in semitones and speed in Hz, none by default.
+//! - `noise`: how much white noise is mixed into the wave, none by default.
+//! - `volume`: 1 by default.
+//!
+//! Rendering doesn't involve any randomness, so a preset always gives the same samples.
+
+use std::{f32::consts::TAU, sync::Arc, time::Duration};
+
+use bevy::{
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    audio::{AddAudioSource, Decodable, Source},
+    prelude::*,
+};
+use strum_macros::EnumString;
+use thiserror::Error;
+
+use crate::audio::noise;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_audio_source::<Synth>()
+        .init_asset_loader::<SynthLoader>();
+}
+
+const SAMPLE_RATE: u32 = 44_100;
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[strum(ascii_case_insensitive)]
+enum Wave {
+    Sine,
+    /// A square wave which is high for the duty cycle of each period.
+    #[strum(disabled)]
+    Square(f32),
+    Saw,
+    Triangle,
+    /// Noise holding each value for half a period, which gives it a pitch.
+    Noise,
+}
+
+#[derive(Debug)]
+struct Preset {
+    wave: Wave,
+    attack: f32,
+    sustain: f32,
+    decay: f32,
+    start_frequency: f32,
+    end_frequency: f32,
+    /// Semitones the vibrato bends the pitch by.
+    vibrato_depth: f32,
+    vibrato_speed: f32,
+    /// Fraction of white noise in the output.
+    noise: f32,
+    volume: f32,
+}
+
+impl Preset {
+    fn duration(&self) -> f32 {
+        self.attack + self.sustain + self.decay
+    }
+
+    /// The envelope `time` seconds into the sound, rising linearly during the attack and falling
+    /// linearly during the decay.
+    fn envelope(&self, time: f32) -> f32 {
+        if time < self.attack {
+            time / self.attack
+        } else if time < self.attack + self.sustain {
+            1.0
+        } else {
+            (1.0 - (time - self.attack - self.sustain) / self.decay).max(0.0)
+        }
+    }
+
+    fn frequency(&self, time: f32) -> f32 {
+        // the slide is exponential, so it sounds even
+        let slide = (self.end_frequency / self.start_frequency).powf(time / self.duration());
+        let vibrato = self.vibrato_depth * (self.vibrato_speed * time * TAU).sin();
+        self.start_frequency * slide * 2f32.powf(vibrato / 12.0)
+    }
+
+    fn render(&self) -> Vec<f32> {
+        let length = (self.duration() * SAMPLE_RATE as f32).ceil() as usize;
+        // in periods, f64 so long sounds don't lose precision
+        let mut phase = 0.0f64;
+
+        (0..length)
+            .map(|sample| {
+                let time = sample as f32 / SAMPLE_RATE as f32;
+                let fraction = phase.fract() as f32;
+                let wave = match self.wave {
+                    Wave::Sine => (fraction * TAU).sin(),
+                    Wave::Square(duty) => {
+                        if fraction < duty {
+                            1.0
+                        } else {
+                            -1.0
+                        }
+                    }
+                    Wave::Saw => 2.0 * fraction - 1.0,
+                    Wave::Triangle => 1.0 - 4.0 * (fraction - 0.5).abs(),
+                    Wave::Noise => noise((phase * 2.0) as u64),
+                };
+                let mixed = wave.lerp(noise(sample as u64), self.noise);
+                phase += self.frequency(time) as f64 / SAMPLE_RATE as f64;
+
+                (self.volume * self.envelope(time) * mixed).clamp(-1.0, 1.0)
+            })
+            .collect()
+    }
+}
+
+/// A sound effect rendered from a preset.
+#[derive(Asset, TypePath, Clone)]
+pub struct Synth {
+    samples: Arc<[f32]>,
+}
+
+impl Decodable for Synth {
+    type DecoderItem = f32;
+    type Decoder = SynthDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        SynthDecoder {
+            samples: self.samples.clone(),
+            position: 0,
+        }
+    }
+}
+
+pub struct SynthDecoder {
+    samples: Arc<[f32]>,
+    position: usize,
+}
+
+impl Iterator for SynthDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.samples.get(self.position).copied();
+        self.position += 1;
+        sample
+    }
+}
+
+impl Source for SynthDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some(self.samples.len().saturating_sub(self.position))
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(
+            self.samples.len() as f32 / SAMPLE_RATE as f32,
+        ))
+    }
+}
+
+#[derive(Default)]
+struct SynthLoader;
+
+#[derive(Error, Debug)]
+enum SynthLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {0}: expected `parameter | values`.")]
+    InvalidFormat(usize),
+    #[error("Line {0}: `{1}` is not a valid number.")]
+    InvalidNumber(usize, String),
+    #[error("Line {0}: unknown wave `{1}`.")]
+    InvalidWave(usize, String),
+    #[error("Line {0}: unknown parameter `{1}`.")]
+    UnknownParameter(usize, String),
+    #[error("The preset needs a `{0}` line.")]
+    MissingParameter(&'static str),
+}
+
+impl AssetLoader for SynthLoader {
+    type Asset = Synth;
+    type Settings = ();
+    type Error = SynthLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+
+        Ok(Synth {
+            samples: parse(&text)?.render().into(),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sfx"]
+    }
+}
+
+/// Parses the text of a `.sfx` file.
+fn parse(text: &str) -> Result<Preset, SynthLoaderError> {
+    let mut wave = Wave::Sine;
+    let mut envelope = None;
+    let mut pitch = None;
+    let (mut vibrato_depth, mut vibrato_speed) = (0.0, 0.0);
+    let mut noise = 0.0;
+    let mut volume = 1.0;
+    for (line, l) in text
+        .lines()
+        .enumerate()
+        .map(|(i, l)| (i + 1, l.trim()))
+        .filter(|(_, l)| !(l.is_empty() || l.starts_with("#")))
+    {
+        let mut columns = l.split('|').map(|t| t.trim());
+        let parameter = columns.next().unwrap();
+        let values: Vec<_> = columns.collect();
+        let number = |index: usize| {
+            let text = values
+                .get(index)
+                .ok_or(SynthLoaderError::InvalidFormat(line))?;
+            text.parse::<f32>()
+                .ok()
+                .filter(|number| *number >= 0.0)
+                .ok_or_else(|| SynthLoaderError::InvalidNumber(line, text.to_string()))
+        };
+        let positive = |index: usize| match number(index)? {
+            0.0 => Err(SynthLoaderError::InvalidNumber(line, values[index].into())),
+            number => Ok(number),
+        };
+        let expect_values = |count: std::ops::RangeInclusive<usize>| {
+            if count.contains(&values.len()) {
+                Ok(())
+            } else {
+                Err(SynthLoaderError::InvalidFormat(line))
+            }
+        };
+
+        match parameter.to_ascii_lowercase().as_str() {
+            "wave" => {
+                expect_values(1..=2)?;
+                let name = values[0];
+                wave = if name.eq_ignore_ascii_case("square") {
+                    let duty = if values.len() == 2 { number(1)? } else { 0.5 };
+                    Wave::Square(duty)
+                } else {
+                    expect_values(1..=1)?;
+                    name.parse()
+                        .map_err(|_| SynthLoaderError::InvalidWave(line, name.into()))?
+                };
+            }
+            "envelope" => {
+                expect_values(3..=3)?;
+                // the decay has to be positive, so the sound doesn't end abruptly
+                envelope = Some((number(0)?, number(1)?, positive(2)?));
+            }
+            "pitch" => {
+                expect_values(1..=2)?;
+                let start = positive(0)?;
+                let end = if values.len() == 2 {
+                    positive(1)?
+                } else {
+                    start
+                };
+                pitch = Some((start, end));
+            }
+            "vibrato" => {
+                expect_values(2..=2)?;
+                (vibrato_depth, vibrato_speed) = (number(0)?, number(1)?);
+            }
+            "noise" => {
+                expect_values(1..=1)?;
+                noise = number(0)?.min(1.0);
+            }
+            "volume" => {
+                expect_values(1..=1)?;
+                volume = number(0)?;
+            }
+            _ => return Err(SynthLoaderError::UnknownParameter(line, parameter.into())),
+        }
+    }
+
+    let (attack, sustain, decay) =
+        envelope.ok_or(SynthLoaderError::MissingParameter("envelope"))?;
+    let (start_frequency, end_frequency) =
+        pitch.ok_or(SynthLoaderError::MissingParameter("pitch"))?;
+    Ok(Preset {
+        wave,
+        attack,
+        sustain,
+        decay,
+        start_frequency,
+        end_frequency,
+        vibrato_depth,
+        vibrato_speed,
+        noise,
+        volume,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRESET: &str = "
+        wave     | square | 0.3
+        envelope | 0.01 | 0.05 | 0.2
+        pitch    | 880 | 440
+        vibrato  | 0.5 | 12
+        noise    | 0.1
+        volume   | 0.6
+    ";
+
+    #[test]
+    fn render_is_deterministic() {
+        let samples = parse(PRESET).unwrap().render();
+        assert_eq!(samples.len(), 11_466);
+
+        // Quantized to 16 bits, so the hash doesn't depend on the last bits of the platform's
+        // `sin` and `powf`. FNV-1a, unlike `DefaultHasher` it's the same across Rust versions.
+        let hash = samples
+            .iter()
+            .map(|sample| (sample * i16::MAX as f32).round() as i16)
+            .flat_map(i16::to_le_bytes)
+            .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100_0000_01b3)
+            });
+        assert_eq!(hash, 3_273_976_574_831_178_694);
+    }
+
+    #[test]
+    fn shipped_presets_parse() {
+        for directory in [
+            "assets/audio/sound_effects",
+            "assets/audio/sound_effects/synth",
+        ] {
+            for entry in std::fs::read_dir(directory).unwrap() {
+                let path = entry.unwrap().path();
+                if path.extension().is_some_and(|extension| extension == "sfx") {
+                    let text = std::fs::read_to_string(&path).unwrap();
+                    if let Err(error) = parse(&text) {
+                        panic!("{}: {error}", path.display());
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn missing_pitch() {
+        let error = parse("envelope | 0.01 | 0.05 | 0.2").unwrap_err();
+        assert!(matches!(error, SynthLoaderError::MissingParameter("pitch")));
+    }
+}
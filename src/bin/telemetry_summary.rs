@@ -0,0 +1,184 @@
+//! Aggregates a folder of telemetry logs written by the game.
+//!
+//! Usage: `cargo run --bin telemetry_summary -- [folder]`, the folder defaults to `telemetry`.
+
+use std::{collections::HashMap, fs, path::Path, process::ExitCode};
+
+fn main() -> ExitCode {
+    let folder = std::env::args().nth(1).unwrap_or("telemetry".to_string());
+
+    let entries = match fs::read_dir(&folder) {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("Could not read {folder}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut summary = Summary::default();
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "jsonl")
+        })
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        if let Err(error) = summary.add_run(&path) {
+            eprintln!("Skipping {}: {error}", path.display());
+        }
+    }
+
+    summary.print();
+    ExitCode::SUCCESS
+}
+
+#[derive(Default)]
+struct Summary {
+    runs: u32,
+    total_length: f32,
+    longest: f32,
+    outcomes: HashMap<String, u32>,
+    clicks: u32,
+    hits: u32,
+    /// How many runs got to each mechanic.
+    mechanics: HashMap<String, u32>,
+    threshold_crossings: HashMap<String, u32>,
+}
+
+impl Summary {
+    fn add_run(&mut self, path: &Path) -> Result<(), String> {
+        let text = fs::read_to_string(path).map_err(|error| error.to_string())?;
+
+        let mut length = 0.0_f32;
+        let mut outcome = "Unfinished".to_string();
+        let mut mechanics = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record =
+                parse_record(line).ok_or(format!("invalid record on line {}", number + 1))?;
+            let field = |key: &str| record.get(key).map(String::as_str).unwrap_or_default();
+
+            length = length.max(field("t").parse().unwrap_or_default());
+            match field("event") {
+                "click" => {
+                    self.clicks += 1;
+                    if field("hit") == "true" {
+                        self.hits += 1;
+                    }
+                }
+                "transition" if field("state") == "GameMechanic" => {
+                    mechanics.push(field("to").to_string());
+                }
+                "bar_threshold" => {
+                    let key = format!("{} {}", field("bar"), field("direction"));
+                    *self.threshold_crossings.entry(key).or_default() += 1;
+                }
+                "game_over" => outcome = field("reason").to_string(),
+                _ => {}
+            }
+        }
+
+        mechanics.sort();
+        mechanics.dedup();
+        for mechanic in mechanics {
+            *self.mechanics.entry(mechanic).or_default() += 1;
+        }
+
+        self.runs += 1;
+        self.total_length += length;
+        self.longest = self.longest.max(length);
+        *self.outcomes.entry(outcome).or_default() += 1;
+        Ok(())
+    }
+
+    fn print(&self) {
+        println!("Runs: {}", self.runs);
+        if self.runs == 0 {
+            return;
+        }
+
+        println!(
+            "Average length: {:.1}s (longest {:.1}s)",
+            self.total_length / self.runs as f32,
+            self.longest
+        );
+        let accuracy = 100.0 * self.hits as f32 / self.clicks.max(1) as f32;
+        println!("Clicks: {} ({accuracy:.0}% on target)", self.clicks);
+
+        print_counts("Outcomes", &self.outcomes);
+        print_counts("Runs reaching each mechanic", &self.mechanics);
+        print_counts("Bar threshold crossings", &self.threshold_crossings);
+    }
+}
+
+fn print_counts(title: &str, counts: &HashMap<String, u32>) {
+    println!("{title}:");
+
+    let mut counts: Vec<_> = counts.iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    for (key, count) in counts {
+        println!("  {key:<24} {count}");
+    }
+}
+
+/// Parses one flat JSON object as written by the game, values are kept as their raw text.
+fn parse_record(line: &str) -> Option<HashMap<String, String>> {
+    let mut chars = line
+        .trim()
+        .strip_prefix('{')?
+        .strip_suffix('}')?
+        .chars()
+        .peekable();
+    let mut record = HashMap::new();
+
+    loop {
+        while chars
+            .next_if(|char| char.is_whitespace() || *char == ',')
+            .is_some()
+        {}
+        if chars.peek().is_none() {
+            return Some(record);
+        }
+
+        chars.next_if_eq(&'"')?;
+        let key = parse_string(&mut chars)?;
+        while chars.next_if(|char| char.is_whitespace()).is_some() {}
+        chars.next_if_eq(&':')?;
+        while chars.next_if(|char| char.is_whitespace()).is_some() {}
+
+        let value = if chars.next_if_eq(&'"').is_some() {
+            parse_string(&mut chars)?
+        } else {
+            let mut value = String::new();
+            while let Some(char) = chars.next_if(|char| *char != ',') {
+                value.push(char);
+            }
+            value.trim().to_string()
+        };
+        record.insert(key, value);
+    }
+}
+
+/// Parses the rest of a string literal whose opening quote was already consumed.
+fn parse_string(chars: &mut impl Iterator<Item = char>) -> Option<String> {
+    let mut string = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(string),
+            '\\' => match chars.next()? {
+                'n' => string.push('\n'),
+                'u' => {
+                    let code: String = chars.by_ref().take(4).collect();
+                    string.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                char => string.push(char),
+            },
+            char => string.push(char),
+        }
+    }
+}
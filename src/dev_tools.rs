@@ -6,9 +6,23 @@ use bevy::{
     prelude::*,
     ui::UiDebugOptions,
 };
-use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::FilterQueryInspectorPlugin};
+use bevy_inspector_egui::{
+    bevy_egui::{EguiContexts, EguiPlugin, EguiPrimaryContextPass, egui},
+    quick::FilterQueryInspectorPlugin,
+};
+use strum::IntoEnumIterator;
 
-use crate::screens::Screen;
+use crate::{
+    audio::beat_clock::BeatClock,
+    game::{
+        game_sequencer::{
+            ActionSequence, EnterMechanic, GameMechanic, RetireMechanic, SequencerState,
+            SkipToAction,
+        },
+        god_mode::GodMode,
+    },
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
@@ -25,10 +39,104 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         toggle_debug_ui.run_if(input_just_pressed(TOGGLE_KEY)),
     );
+
+    // Mechanic console for jumping around the sequence while testing.
+    app.add_systems(
+        EguiPrimaryContextPass,
+        mechanic_console
+            .run_if(in_state(Screen::Gameplay).and(input_toggle_active(false, CONSOLE_TOGGLE_KEY))),
+    );
 }
 
 const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const CONSOLE_TOGGLE_KEY: KeyCode = KeyCode::F1;
+const CLOCK_SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
 
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+fn mechanic_console(
+    mut contexts: EguiContexts,
+    mut commands: Commands,
+    mut time: ResMut<Time<Virtual>>,
+    mut god_mode: ResMut<GodMode>,
+    state: Option<Res<SequencerState>>,
+    action_sequences: Res<Assets<ActionSequence>>,
+    beat_clock: Option<Res<BeatClock>>,
+) -> Result {
+    let info = state.map(|state| state.info(&action_sequences, beat_clock.as_deref()));
+
+    egui::Window::new("Mechanic console").show(contexts.ctx_mut()?, |ui| {
+        egui::Grid::new("mechanics").show(ui, |ui| {
+            for mechanic in GameMechanic::iter().filter(|mechanic| *mechanic != GameMechanic::None)
+            {
+                let active = info
+                    .as_ref()
+                    .is_some_and(|info| info.active_mechanics.contains(&mechanic));
+                ui.label(if active {
+                    egui::RichText::new(mechanic.as_ref()).strong()
+                } else {
+                    egui::RichText::new(mechanic.as_ref())
+                });
+                if ui.button("Spawn").clicked() {
+                    commands.trigger(EnterMechanic(mechanic));
+                }
+                if ui.button("Retire").clicked() {
+                    commands.trigger(RetireMechanic(mechanic));
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Clock");
+            for speed in CLOCK_SPEEDS {
+                let selected = time.relative_speed() == speed;
+                if ui.selectable_label(selected, format!("{speed}x")).clicked() {
+                    time.set_relative_speed(speed);
+                }
+            }
+        });
+
+        let mut enabled = god_mode.enabled;
+        if ui.checkbox(&mut enabled, "God mode (F2)").changed() {
+            god_mode.enabled = enabled;
+        }
+
+        let Some(info) = info else {
+            return;
+        };
+
+        ui.separator();
+        ui.label(format!(
+            "Action {}/{}",
+            info.action_index,
+            info.actions.len()
+        ));
+        if let Some(next_action_in) = info.next_action_in {
+            ui.label(format!("Next action in {next_action_in:.1}s"));
+        }
+
+        egui::ScrollArea::vertical()
+            .max_height(240.0)
+            .show(ui, |ui| {
+                for (index, (delay, description)) in info.actions.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let text = format!("{index:>3} +{delay:<4} {description}");
+                        if index == info.action_index {
+                            ui.label(egui::RichText::new(text).strong());
+                        } else {
+                            ui.label(text);
+                        }
+                        if index > info.action_index && ui.small_button("Skip here").clicked() {
+                            commands.trigger(SkipToAction(index));
+                        }
+                    });
+                }
+            });
+    });
+
+    Ok(())
+}
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    prelude::*,
+    ui::Val::*,
+};
+use itertools::Itertools;
+use strum_macros::EnumString;
+use thiserror::Error;
+
+use crate::{
+    PausableSystems,
+    asset_tracking::LoadResource,
+    audio::sfx::PlaySfx,
+    game::{
+        GameOver,
+        bar::Bar,
+        game_sequencer::GameMechanic,
+        mechanics::{
+            durability::OnFixClicked,
+            pentagon::Pentagon,
+            the_button::{ButtonTimeBar, OnButtonClicked},
+            triangles::OnTriangleDestroyed,
+        },
+        player::Player,
+        practice::PracticeRun,
+    },
+    localization::LocalizedText,
+    screens::Screen,
+};
+
+/// Distance between the pentagon and the player which counts as "point-blank range".
+const POINT_BLANK_DISTANCE: f32 = 128.0;
+
+const TOAST_DURATION: f32 = 4.0;
+const TOAST_HEIGHT: f32 = 90.0;
+const TOAST_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
+const TOAST_TITLE_COLOR: Color = Color::linear_rgb(1.0, 1.0, 0.0);
+
+#[cfg(not(target_family = "wasm"))]
+const SAVE_FILE: &str = "achievements.sav";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<AchievementList>()
+        .init_asset_loader::<AchievementListLoader>()
+        .load_resource::<AchievementAssets>()
+        .init_resource::<RunStats>()
+        .insert_resource(AchievementProgress::load())
+        .add_systems(OnEnter(Screen::Gameplay), reset_run_stats)
+        .add_systems(
+            Update,
+            (
+                (track_time_bar, track_pentagon_distance, check_achievements)
+                    .chain()
+                    .in_set(PausableSystems)
+                    .run_if(in_state(Screen::Gameplay)),
+                update_toasts,
+            ),
+        )
+        .add_observer(count_button_clicks)
+        .add_observer(count_destroyed_triangles)
+        .add_observer(mark_fix_used)
+        .add_observer(on_run_finished)
+        .add_observer(spawn_toast);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "title_case")]
+#[strum(ascii_case_insensitive)]
+pub enum AchievementCondition {
+    /// Click THE BUTTON `target` times in one run.
+    ButtonClicks,
+    /// Destroy `target` triangles in one run.
+    TrianglesDestroyed,
+    /// Survive `target` seconds (in total) with the pentagon at point-blank range.
+    PentagonPointBlank,
+    /// Win without clicking the FIX button.
+    WinWithoutFix,
+    /// Win without letting the button time bar drop below `target` percent.
+    TimeBarAbove,
+}
+
+impl AchievementCondition {
+    /// Whether the achievement can only be unlocked by winning the run.
+    fn requires_victory(self) -> bool {
+        matches!(self, Self::WinWithoutFix | Self::TimeBarAbove)
+    }
+
+    /// Whether the progress of the achievement is a counter which can be shown as `best/target`.
+    pub fn is_counter(self) -> bool {
+        !self.requires_victory()
+    }
+}
+
+pub struct Achievement {
+    pub id: String,
+    pub condition: AchievementCondition,
+    pub target: f32,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Asset, TypePath)]
+pub struct AchievementList(pub Vec<Achievement>);
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct AchievementAssets {
+    #[dependency]
+    pub list: Handle<AchievementList>,
+}
+
+impl FromWorld for AchievementAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            list: assets.load("achievements.ach"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct AchievementListLoader;
+
+#[derive(Error, Debug)]
+pub enum AchievementListLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {0}: expected `id | condition | target | title | description`.")]
+    InvalidFormat(usize),
+    #[error("Line {0}: unknown achievement condition `{1}`.")]
+    InvalidCondition(usize, String),
+    #[error("Line {0}: invalid target `{1}`.")]
+    InvalidTarget(usize, String),
+}
+
+impl AssetLoader for AchievementListLoader {
+    type Asset = AchievementList;
+    type Settings = ();
+    type Error = AchievementListLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+
+        let achievements = text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !(l.is_empty() || l.starts_with("#")))
+            .map(|(line, l)| {
+                let (id, condition, target, title, description) = l
+                    .split('|')
+                    .map(|t| t.trim())
+                    .collect_tuple()
+                    .ok_or(AchievementListLoaderError::InvalidFormat(line))?;
+
+                Ok::<_, AchievementListLoaderError>(Achievement {
+                    id: id.into(),
+                    condition: condition.parse().map_err(|_| {
+                        AchievementListLoaderError::InvalidCondition(line, condition.into())
+                    })?,
+                    target: target.parse().map_err(|_| {
+                        AchievementListLoaderError::InvalidTarget(line, target.into())
+                    })?,
+                    title: title.into(),
+                    description: description.into(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AchievementList(achievements))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ach"]
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct AchievementRecord {
+    /// Best progress reached in a single run.
+    pub best: f32,
+    pub unlocked: bool,
+}
+
+/// Progress of all achievements across runs.
+#[derive(Resource, Default)]
+pub struct AchievementProgress(pub HashMap<String, AchievementRecord>);
+
+impl AchievementProgress {
+    #[cfg(not(target_family = "wasm"))]
+    fn load() -> Self {
+        let Ok(text) = std::fs::read_to_string(SAVE_FILE) else {
+            return Self::default();
+        };
+
+        let records = text
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !(l.is_empty() || l.starts_with("#")))
+            .filter_map(|l| l.split('|').map(|t| t.trim()).collect_tuple())
+            .filter_map(|(id, best, unlocked)| {
+                let record = AchievementRecord {
+                    best: best.parse().ok()?,
+                    unlocked: unlocked.parse().ok()?,
+                };
+                Some((id.to_string(), record))
+            })
+            .collect();
+
+        Self(records)
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn load() -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn save(&self) {
+        let mut text = String::from("# id | best | unlocked\n");
+        for (id, record) in self.0.iter().sorted_by_key(|(id, _)| *id) {
+            text += &format!("{id} | {} | {}\n", record.best, record.unlocked);
+        }
+
+        if let Err(error) = std::fs::write(SAVE_FILE, text) {
+            warn!("Could not save achievements: {error}");
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn save(&self) {}
+}
+
+#[derive(Event)]
+struct OnAchievementUnlocked {
+    title: String,
+}
+
+/// Statistics of the current run used to evaluate achievements.
+#[derive(Resource)]
+struct RunStats {
+    button_clicks: u32,
+    triangles_destroyed: u32,
+    fix_used: bool,
+    /// Lowest fill of the button time bar, in the range 0..1.
+    min_time_bar: f32,
+    /// Total seconds spent with the pentagon at point-blank range.
+    pentagon_point_blank: f32,
+}
+
+impl Default for RunStats {
+    fn default() -> Self {
+        Self {
+            button_clicks: 0,
+            triangles_destroyed: 0,
+            fix_used: false,
+            min_time_bar: 1.0,
+            pentagon_point_blank: 0.0,
+        }
+    }
+}
+
+impl RunStats {
+    fn progress(&self, condition: AchievementCondition) -> f32 {
+        match condition {
+            AchievementCondition::ButtonClicks => self.button_clicks as f32,
+            AchievementCondition::TrianglesDestroyed => self.triangles_destroyed as f32,
+            AchievementCondition::PentagonPointBlank => self.pentagon_point_blank,
+            AchievementCondition::WinWithoutFix | AchievementCondition::TimeBarAbove => 0.0,
+        }
+    }
+
+    fn is_met(&self, achievement: &Achievement, victory: bool) -> bool {
+        match achievement.condition {
+            AchievementCondition::WinWithoutFix => victory && !self.fix_used,
+            AchievementCondition::TimeBarAbove => {
+                victory && self.min_time_bar * 100.0 >= achievement.target
+            }
+            condition => self.progress(condition) >= achievement.target,
+        }
+    }
+}
+
+#[derive(Component)]
+struct AchievementToast(Timer);
+
+fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+fn count_button_clicks(_: Trigger<OnButtonClicked>, mut stats: ResMut<RunStats>) {
+    stats.button_clicks += 1;
+}
+
+fn count_destroyed_triangles(_: Trigger<OnTriangleDestroyed>, mut stats: ResMut<RunStats>) {
+    stats.triangles_destroyed += 1;
+}
+
+fn mark_fix_used(_: Trigger<OnFixClicked>, mut stats: ResMut<RunStats>) {
+    stats.fix_used = true;
+}
+
+fn track_time_bar(bar: Single<&Bar, With<ButtonTimeBar>>, mut stats: ResMut<RunStats>) {
+    let progress = (bar.current - bar.min) / (bar.max - bar.min);
+    stats.min_time_bar = stats.min_time_bar.min(progress);
+}
+
+fn track_pentagon_distance(
+    pentagon: Single<&Transform, With<Pentagon>>,
+    player: Single<&Transform, With<Player>>,
+    mut stats: ResMut<RunStats>,
+    time: Res<Time>,
+) {
+    let distance = pentagon.translation.xy().distance(player.translation.xy());
+    if distance <= POINT_BLANK_DISTANCE {
+        stats.pentagon_point_blank += time.delta_secs();
+    }
+}
+
+fn check_achievements(
+    mut commands: Commands,
+    stats: Res<RunStats>,
+    mut progress: ResMut<AchievementProgress>,
+    achievement_assets: Res<AchievementAssets>,
+    achievement_lists: Res<Assets<AchievementList>>,
+    practice: Res<PracticeRun>,
+    #[cfg(feature = "dev")] god_mode: Res<super::god_mode::GodMode>,
+) {
+    let Some(list) = achievement_lists.get(&achievement_assets.list) else {
+        return;
+    };
+
+    if practice.0.is_some() {
+        return;
+    }
+    #[cfg(feature = "dev")]
+    if god_mode.enabled {
+        return;
+    }
+
+    for achievement in list.0.iter() {
+        if achievement.condition.requires_victory() {
+            continue;
+        }
+        unlock_if_met(&mut commands, &mut progress, achievement, &stats, false);
+    }
+}
+
+fn on_run_finished(
+    trigger: Trigger<GameOver>,
+    #[cfg(feature = "dev")] god_mode: Res<super::god_mode::GodMode>,
+    mut commands: Commands,
+    stats: Res<RunStats>,
+    mut progress: ResMut<AchievementProgress>,
+    achievement_assets: Res<AchievementAssets>,
+    achievement_lists: Res<Assets<AchievementList>>,
+    practice: Res<PracticeRun>,
+) {
+    let Some(list) = achievement_lists.get(&achievement_assets.list) else {
+        return;
+    };
+
+    // Practice runs and runs played in god mode don't count.
+    if practice.0.is_some() {
+        return;
+    }
+    #[cfg(feature = "dev")]
+    if god_mode.enabled {
+        return;
+    }
+
+    let victory = trigger.event().cause == GameMechanic::Victory;
+    for achievement in list.0.iter() {
+        let record = progress.0.entry(achievement.id.clone()).or_default();
+        record.best = record.best.max(stats.progress(achievement.condition));
+
+        unlock_if_met(&mut commands, &mut progress, achievement, &stats, victory);
+    }
+
+    progress.save();
+}
+
+fn unlock_if_met(
+    commands: &mut Commands,
+    progress: &mut AchievementProgress,
+    achievement: &Achievement,
+    stats: &RunStats,
+    victory: bool,
+) {
+    let record = progress.0.entry(achievement.id.clone()).or_default();
+    if record.unlocked || !stats.is_met(achievement, victory) {
+        return;
+    }
+
+    record.unlocked = true;
+    record.best = record.best.max(stats.progress(achievement.condition));
+    progress.save();
+
+    commands.trigger(OnAchievementUnlocked {
+        title: achievement.title.clone(),
+    });
+}
+
+fn spawn_toast(
+    trigger: Trigger<OnAchievementUnlocked>,
+    mut commands: Commands,
+    toasts: Query<(), With<AchievementToast>>,
+) {
+    commands.spawn((
+        Name::new("Achievement toast"),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Px(20.0),
+            bottom: Px(20.0 + toasts.iter().count() as f32 * TOAST_HEIGHT),
+            padding: UiRect::all(Px(12.0)),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(TOAST_BACKGROUND),
+        GlobalZIndex(3),
+        // Not scoped to gameplay, victory achievements unlock as the game over screen opens.
+        AchievementToast(Timer::from_seconds(TOAST_DURATION, TimerMode::Once)),
+        Pickable::IGNORE,
+        children![
+            (
+                LocalizedText::new("@achievements.toast"),
+                Text::default(),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ),
+            (
+                LocalizedText::new(trigger.event().title.clone()),
+                Text::default(),
+                TextFont {
+                    font_size: 32.0,
+                    ..default()
+                },
+                TextColor(TOAST_TITLE_COLOR),
+            ),
+        ],
+    ));
+
+    commands.trigger(PlaySfx("achievement"));
+}
+
+fn update_toasts(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut AchievementToast)>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut toast) in query.iter_mut() {
+        toast.0.tick(time.delta());
+        if toast.0.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
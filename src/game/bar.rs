@@ -1,8 +1,24 @@
+use std::f32::consts::TAU;
+
 use bevy::prelude::*;
 
+const TICK_WIDTH: f32 = 3.0;
+const ICON_SPACING: f32 = 8.0;
+
+/// How much the flash color is mixed into the bar color at the peak of a warning flash.
+const WARNING_FLASH_STRENGTH: f32 = 0.6;
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_observer(on_add_bar)
-        .add_systems(Update, (clamp_current_value, update_progress).chain());
+    app.add_observer(on_add_bar).add_systems(
+        Update,
+        (
+            clamp_current_value,
+            trigger_thresholds,
+            update_progress,
+            update_color,
+        )
+            .chain(),
+    );
 }
 
 #[derive(Event)]
@@ -15,20 +31,62 @@ pub struct OnBarFull {
     pub _sender: Entity,
 }
 
+/// Triggered when the bar progress crosses one of [`BarBehavior::thresholds`].
+#[derive(Event)]
+pub struct OnBarThreshold {
+    pub sender: Entity,
+    pub threshold: f32,
+    pub crossing_direction: CrossingDirection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossingDirection {
+    /// The bar drained below the threshold.
+    Falling,
+    /// The bar filled above the threshold.
+    Rising,
+}
+
 #[derive(Component, Default)]
-#[require(BarLayout, BarBehavior, BarEntities, Visibility, Transform)]
+#[require(
+    BarLayout,
+    BarBehavior,
+    BarEntities,
+    BarState,
+    BarFill,
+    Visibility,
+    Transform
+)]
 pub struct Bar {
     pub min: f32,
     pub max: f32,
     pub current: f32,
 }
 
+impl Bar {
+    /// Current value mapped to the range 0..1.
+    pub fn progress(&self) -> f32 {
+        (self.current - self.min) / (self.max - self.min)
+    }
+}
+
 #[derive(Component)]
 pub struct BarLayout {
     pub size: Vec2,
     pub color: Color,
+    /// Color of the inner rectangle when the bar is empty. The color is shifted from
+    /// [`Self::color`] towards this one as the bar drains.
+    pub empty_color: Option<Color>,
     pub border_size: f32,
     pub border_color: Color,
+    pub warning: Option<BarWarning>,
+    pub label: Option<BarLabel>,
+    /// Number of segments the bar is split into by tick marks, e.g. one per point of durability.
+    pub segments: Option<u32>,
+    pub tick_color: Color,
+    /// Image shown to the left of the bar.
+    pub icon: Option<Handle<Image>>,
+    pub icon_size: Vec2,
 }
 
 impl Default for BarLayout {
@@ -36,8 +94,69 @@ impl Default for BarLayout {
         Self {
             size: vec2(128.0, 16.0),
             color: Color::linear_rgb(1.0, 0.0, 0.0),
+            empty_color: None,
             border_size: 5.0,
             border_color: Color::linear_rgb(0.0, 0.0, 0.0),
+            warning: None,
+            label: None,
+            segments: None,
+            tick_color: Color::linear_rgb(0.0, 0.0, 0.0),
+            icon: None,
+            icon_size: Vec2::splat(32.0),
+        }
+    }
+}
+
+/// Text rendered over the bar, kept in sync with [`Bar::current`].
+pub struct BarLabel {
+    pub format: BarLabelFormat,
+    /// Chosen by the [`FontRegistry`](crate::theme::font::FontRegistry) if left at the default.
+    pub font: Handle<Font>,
+    pub font_size: f32,
+    pub color: Color,
+}
+
+impl Default for BarLabel {
+    fn default() -> Self {
+        Self {
+            format: BarLabelFormat::Value,
+            font: default(),
+            font_size: 16.0,
+            color: Color::linear_rgb(0.0, 0.0, 0.0),
+        }
+    }
+}
+
+pub enum BarLabelFormat {
+    /// Current and max value, e.g. `4/6`.
+    Value,
+    Custom(fn(&Bar) -> String),
+}
+
+impl BarLabelFormat {
+    fn format(&self, bar: &Bar) -> String {
+        match self {
+            Self::Value => format!("{:.0}/{:.0}", bar.current.ceil(), bar.max),
+            Self::Custom(format) => format(bar),
+        }
+    }
+}
+
+/// Flashes the inner rectangle while the bar progress is below the threshold.
+pub struct BarWarning {
+    /// Progress (0..1) below which the bar starts flashing.
+    pub threshold: f32,
+    /// Flashes per second.
+    pub frequency: f32,
+    pub flash_color: Color,
+}
+
+impl Default for BarWarning {
+    fn default() -> Self {
+        Self {
+            threshold: 0.25,
+            frequency: 4.0,
+            flash_color: Color::WHITE,
         }
     }
 }
@@ -46,12 +165,94 @@ impl Default for BarLayout {
 pub struct BarBehavior {
     pub trigger_on_full: bool,
     pub trigger_on_empty: bool,
+    /// Progress values (0..1) which trigger [`OnBarThreshold`] when crossed.
+    pub thresholds: Vec<f32>,
+}
+
+/// Animates the fill of a [`Bar`] towards [`Bar::current`] instead of jumping to it. A trailing
+/// "ghost" segment shows the most recent change of the value.
+#[derive(Component)]
+pub struct BarAnimation {
+    /// Seconds it takes the fill to reach a new value.
+    pub duration: f32,
+    pub easing: EaseFunction,
+    /// Seconds the ghost segment lingers before it catches up with the new value.
+    pub ghost_delay: f32,
+    /// Seconds it takes the ghost segment to catch up once it starts moving.
+    pub ghost_duration: f32,
+    /// Color of the ghost segment when the value decreases.
+    pub decrease_color: Color,
+    /// Color of the ghost segment when the value increases.
+    pub increase_color: Color,
+}
+
+impl Default for BarAnimation {
+    fn default() -> Self {
+        Self {
+            duration: 0.15,
+            easing: EaseFunction::QuadraticOut,
+            ghost_delay: 0.4,
+            ghost_duration: 0.3,
+            decrease_color: Color::WHITE,
+            increase_color: Color::linear_rgb(0.5, 1.0, 0.5),
+        }
+    }
+}
+
+#[derive(Component, Default)]
+struct BarState {
+    /// Progress from the previous update, used to detect threshold crossings.
+    previous_progress: Option<f32>,
+}
+
+/// Displayed progress of the fill and of the ghost segment.
+#[derive(Component, Default)]
+struct BarFill {
+    target: Option<f32>,
+    fill: Tween,
+    ghost: Tween,
+}
+
+#[derive(Default)]
+struct Tween {
+    from: f32,
+    to: f32,
+    /// Seconds to wait before the tween starts.
+    delay: f32,
+    duration: f32,
+    elapsed: f32,
+}
+
+impl Tween {
+    fn new(from: f32, to: f32, delay: f32, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            delay,
+            duration,
+            elapsed: 0.0,
+        }
+    }
+
+    fn value(&self, easing: EaseFunction) -> f32 {
+        let t = if self.duration > 0.0 {
+            (self.elapsed - self.delay) / self.duration
+        } else if self.elapsed >= self.delay {
+            1.0
+        } else {
+            0.0
+        };
+
+        EasingCurve::new(self.from, self.to, easing).sample_clamped(t)
+    }
 }
 
 #[derive(Component)]
 struct BarEntities {
     outer: Entity,
     inner: Entity,
+    ghost: Entity,
+    label: Option<Entity>,
 }
 
 impl Default for BarEntities {
@@ -59,6 +260,8 @@ impl Default for BarEntities {
         Self {
             outer: Entity::PLACEHOLDER,
             inner: Entity::PLACEHOLDER,
+            ghost: Entity::PLACEHOLDER,
+            label: None,
         }
     }
 }
@@ -66,11 +269,12 @@ impl Default for BarEntities {
 fn on_add_bar(
     trigger: Trigger<OnAdd, Bar>,
     mut commands: Commands,
-    mut query: Query<(Entity, &mut BarEntities, &BarLayout), With<Bar>>,
+    mut query: Query<(Entity, &mut BarEntities, &BarLayout, &Bar)>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    let (entity, mut bar_entities, bar_layout) = query.get_mut(trigger.target()).unwrap();
+    let (entity, mut bar_entities, bar_layout, bar) = query.get_mut(trigger.target()).unwrap();
+    let inner_size = bar_layout.size - Vec2::splat(bar_layout.border_size);
 
     commands.entity(entity).with_children(|parent| {
         // outer rectangle
@@ -84,13 +288,23 @@ fn on_add_bar(
                 },
             ))
             .with_children(|parent| {
+                // ghost rectangle
+                bar_entities.ghost = parent
+                    .spawn((
+                        Mesh2d(meshes.add(Rectangle::from_size(inner_size))),
+                        MeshMaterial2d(materials.add(Color::NONE)),
+                        Transform::from_xyz(0.0, 0.0, 0.25),
+                        Pickable {
+                            should_block_lower: false,
+                            ..default()
+                        },
+                    ))
+                    .id();
+
                 // inner rectangle
                 bar_entities.inner = parent
                     .spawn((
-                        Mesh2d(meshes.add(Rectangle::new(
-                            bar_layout.size.x - bar_layout.border_size,
-                            bar_layout.size.y - bar_layout.border_size,
-                        ))),
+                        Mesh2d(meshes.add(Rectangle::from_size(inner_size))),
                         MeshMaterial2d(materials.add(bar_layout.color)),
                         Transform::from_xyz(0.0, 0.0, 0.5),
                         Pickable {
@@ -99,6 +313,54 @@ fn on_add_bar(
                         },
                     ))
                     .id();
+
+                // segment ticks
+                let segments = bar_layout.segments.unwrap_or(1);
+                for i in 1..segments {
+                    let x = inner_size.x * (i as f32 / segments as f32 - 0.5);
+                    parent.spawn((
+                        Mesh2d(meshes.add(Rectangle::new(TICK_WIDTH, inner_size.y))),
+                        MeshMaterial2d(materials.add(bar_layout.tick_color)),
+                        Transform::from_xyz(x, 0.0, 0.75),
+                        Pickable {
+                            should_block_lower: false,
+                            ..default()
+                        },
+                    ));
+                }
+
+                // label
+                bar_entities.label = bar_layout.label.as_ref().map(|label| {
+                    parent
+                        .spawn((
+                            Text2d::new(label.format.format(bar)),
+                            TextFont {
+                                font: label.font.clone(),
+                                font_size: label.font_size,
+                                ..default()
+                            },
+                            TextLayout::new_with_justify(JustifyText::Center),
+                            TextColor(label.color),
+                            Transform::from_xyz(0.0, 0.0, 1.0),
+                        ))
+                        .id()
+                });
+
+                // icon
+                if let Some(icon) = &bar_layout.icon {
+                    parent.spawn((
+                        Sprite {
+                            image: icon.clone(),
+                            custom_size: Some(bar_layout.icon_size),
+                            ..default()
+                        },
+                        Transform::from_xyz(
+                            -(bar_layout.size.x + bar_layout.icon_size.x) * 0.5 - ICON_SPACING,
+                            0.0,
+                            0.0,
+                        ),
+                    ));
+                }
             })
             .id();
     });
@@ -120,14 +382,157 @@ fn clamp_current_value(
     }
 }
 
+fn trigger_thresholds(
+    mut commands: Commands,
+    mut query: Query<(&Bar, &BarBehavior, &mut BarState, Entity), Changed<Bar>>,
+) {
+    for (bar, bar_behavior, mut state, entity) in query.iter_mut() {
+        let progress = bar.progress();
+        let Some(previous_progress) = state.previous_progress.replace(progress) else {
+            continue;
+        };
+
+        for &threshold in bar_behavior.thresholds.iter() {
+            let crossing_direction = if previous_progress >= threshold && progress < threshold {
+                CrossingDirection::Falling
+            } else if previous_progress < threshold && progress >= threshold {
+                CrossingDirection::Rising
+            } else {
+                continue;
+            };
+
+            commands.trigger(OnBarThreshold {
+                sender: entity,
+                threshold,
+                crossing_direction,
+            });
+        }
+    }
+}
+
 fn update_progress(
-    bar_query: Query<(&Bar, &BarLayout, &BarEntities), Changed<Bar>>,
+    mut bar_query: Query<(
+        &Bar,
+        &BarLayout,
+        &BarEntities,
+        &mut BarFill,
+        Option<&BarAnimation>,
+    )>,
     mut transform_query: Query<&mut Transform>,
+    mut text_query: Query<&mut Text2d>,
+    material_query: Query<&MeshMaterial2d<ColorMaterial>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
 ) {
-    for (bar, _layout, entities) in bar_query.iter() {
-        let progress = (bar.current - bar.min) / (bar.max - bar.min);
-        let mut transform = transform_query.get_mut(entities.inner).unwrap();
+    for (bar, layout, entities, mut fill, animation) in bar_query.iter_mut() {
+        // update label
+        if let Some((label, entity)) = layout.label.as_ref().zip(entities.label) {
+            let mut text = text_query.get_mut(entity).unwrap();
+            let value = label.format.format(bar);
+            if text.0 != value {
+                text.0 = value;
+            }
+        }
+
+        let progress = bar.progress();
+        let previous_target = fill.target.replace(progress);
+
+        let (fill_progress, ghost_progress) = match animation {
+            Some(animation) => {
+                fill.fill.elapsed += time.delta_secs();
+                fill.ghost.elapsed += time.delta_secs();
+
+                let fill_progress = fill.fill.value(animation.easing);
+                let ghost_progress = fill.ghost.value(animation.easing);
+
+                if previous_target.is_some_and(|target| target != progress) {
+                    let (ghost_color, fill_delay, ghost_delay) = if progress < fill_progress {
+                        // fill drops right away, ghost lingers at the old value
+                        (animation.decrease_color, 0.0, animation.ghost_delay)
+                    } else {
+                        // ghost jumps to the new value, fill follows later
+                        (animation.increase_color, animation.ghost_delay, 0.0)
+                    };
+
+                    fill.fill = Tween::new(fill_progress, progress, fill_delay, animation.duration);
+                    fill.ghost = Tween::new(
+                        ghost_progress.max(fill_progress),
+                        progress,
+                        ghost_delay,
+                        if ghost_delay > 0.0 {
+                            animation.ghost_duration
+                        } else {
+                            0.0
+                        },
+                    );
+
+                    if let Some(material) = material_query
+                        .get(entities.ghost)
+                        .ok()
+                        .and_then(|material| materials.get_mut(&material.0))
+                    {
+                        material.color = ghost_color;
+                    }
+                } else if previous_target.is_none() {
+                    fill.fill = Tween::new(progress, progress, 0.0, 0.0);
+                    fill.ghost = Tween::new(progress, progress, 0.0, 0.0);
+                }
+
+                (
+                    fill.fill.value(animation.easing),
+                    fill.ghost.value(animation.easing),
+                )
+            }
+            None => (progress, 0.0),
+        };
+
+        let inner_width = layout.size.x - layout.border_size;
+        for (entity, progress) in [
+            (entities.inner, fill_progress),
+            (entities.ghost, ghost_progress),
+        ] {
+            let mut transform = transform_query.get_mut(entity).unwrap();
 
-        transform.scale.x = progress;
+            // anchor to the left edge
+            transform.scale.x = progress;
+            transform.translation.x = -inner_width * 0.5 * (1.0 - progress);
+        }
+    }
+}
+
+fn update_color(
+    bar_query: Query<(&Bar, &BarLayout, &BarEntities)>,
+    material_query: Query<&MeshMaterial2d<ColorMaterial>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    for (bar, layout, entities) in bar_query.iter() {
+        if layout.empty_color.is_none() && layout.warning.is_none() {
+            continue;
+        }
+
+        let progress = bar.progress();
+
+        // shift color along the gradient
+        let mut color = match layout.empty_color {
+            Some(empty_color) => empty_color.mix(&layout.color, progress),
+            None => layout.color,
+        };
+
+        // flash when below the warning threshold
+        if let Some(warning) = layout.warning.as_ref().filter(|w| progress < w.threshold) {
+            let phase = (time.elapsed_secs() * warning.frequency * TAU).sin();
+            color = color.mix(
+                &warning.flash_color,
+                0.5 * (phase + 1.0) * WARNING_FLASH_STRENGTH,
+            );
+        }
+
+        let Ok(material) = material_query.get(entities.inner) else {
+            continue;
+        };
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = color;
+        }
     }
 }
@@ -0,0 +1,121 @@
+//! Hit-stop before the game over screen. The run freezes for a moment and the culprit is
+//! highlighted, so players can see what killed them.
+
+use bevy::{prelude::*, render::primitives::Aabb};
+
+use crate::{
+    Pause,
+    game::game_sequencer::GameMechanic,
+    screens::{Screen, game_over::GameOverData},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            begin_freeze.run_if(resource_added::<DeathFreeze>),
+            (animate_highlight, end_freeze).run_if(resource_exists::<DeathFreeze>),
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    )
+    .add_systems(OnExit(Screen::Gameplay), remove_freeze);
+}
+
+/// Real-time seconds the run stays frozen before the game over screen.
+const FREEZE_DURATION: f32 = 0.8;
+const HIGHLIGHT_COLOR: Color = Color::linear_rgb(1.0, 1.0, 1.0);
+const HIGHLIGHT_WIDTH: f32 = 6.0;
+/// Radius of the highlight around culprits without bounds.
+const DEFAULT_HIGHLIGHT_RADIUS: f32 = 48.0;
+/// Gap between the culprit's bounds and the highlight ring.
+const HIGHLIGHT_PADDING: f32 = 12.0;
+const HIGHLIGHT_Z: f32 = 10.0;
+/// Pulses per second of the highlight ring.
+const HIGHLIGHT_PULSE_SPEED: f32 = 4.0;
+
+/// Present while the run is frozen after a game over.
+#[derive(Resource)]
+pub struct DeathFreeze {
+    cause: GameMechanic,
+    culprit: Option<Entity>,
+    timer: Timer,
+}
+
+impl DeathFreeze {
+    pub fn new(cause: GameMechanic, culprit: Option<Entity>) -> Self {
+        Self {
+            cause,
+            culprit,
+            timer: Timer::from_seconds(FREEZE_DURATION, TimerMode::Once),
+        }
+    }
+}
+
+#[derive(Component)]
+struct CulpritHighlight;
+
+fn begin_freeze(
+    mut commands: Commands,
+    freeze: Res<DeathFreeze>,
+    culprits: Query<Option<&Aabb>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    // Stops every gameplay system, so nothing moves and the culprit can't trigger again.
+    next_pause.set(Pause(true));
+
+    let Some((culprit, bounds)) = freeze
+        .culprit
+        .and_then(|culprit| culprits.get(culprit).ok().map(|bounds| (culprit, bounds)))
+    else {
+        return;
+    };
+
+    let radius = bounds.map_or(DEFAULT_HIGHLIGHT_RADIUS, |bounds| {
+        bounds.half_extents.truncate().length()
+    }) + HIGHLIGHT_PADDING;
+
+    commands.entity(culprit).with_child((
+        Name::new("Culprit highlight"),
+        Mesh2d(meshes.add(Annulus::new(radius, radius + HIGHLIGHT_WIDTH))),
+        MeshMaterial2d(materials.add(HIGHLIGHT_COLOR)),
+        Transform::from_xyz(0.0, 0.0, HIGHLIGHT_Z),
+        CulpritHighlight,
+        Pickable::IGNORE,
+    ));
+}
+
+fn animate_highlight(
+    mut highlights: Query<&mut Transform, With<CulpritHighlight>>,
+    time: Res<Time<Real>>,
+) {
+    // Runs on real time, like the freeze itself.
+    let phase = time.elapsed_secs() * HIGHLIGHT_PULSE_SPEED * std::f32::consts::TAU;
+    let scale = 1.0 + 0.1 * phase.sin();
+
+    for mut transform in &mut highlights {
+        transform.scale = Vec3::splat(scale);
+    }
+}
+
+fn end_freeze(
+    mut commands: Commands,
+    mut freeze: ResMut<DeathFreeze>,
+    mut game_over_data: ResMut<GameOverData>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    time: Res<Time<Real>>,
+) {
+    if !freeze.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    game_over_data.reason = freeze.cause;
+    next_screen.set(Screen::GameOver);
+    commands.remove_resource::<DeathFreeze>();
+}
+
+fn remove_freeze(mut commands: Commands) {
+    commands.remove_resource::<DeathFreeze>();
+}
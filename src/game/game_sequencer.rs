@@ -1,22 +1,56 @@
+use std::collections::VecDeque;
+
 use bevy::{
     asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
     prelude::*,
 };
 use itertools::Itertools;
-use strum_macros::EnumString;
+use strum_macros::{AsRefStr, EnumIter, EnumString};
 use thiserror::Error;
 
-use crate::{PausableSystems, audio::sound_effect, game::guide::GuideText, screens::Screen};
+use crate::{
+    PausableSystems,
+    audio::beat_clock::BeatClock,
+    game::{
+        guide::{ClearGuide, GuideMessage},
+        mechanics::timer::ElapsedTime,
+        practice::PracticeRun,
+    },
+    screens::Screen,
+    theme::markup::{self, MarkupError},
+};
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MechanicEntity>();
     app.init_state::<GameMechanic>()
+        .init_resource::<LastCheckpoint>()
+        .init_resource::<RestoreCheckpoint>()
+        .add_observer(restart_from_checkpoint)
         .init_asset::<ActionSequence>()
         .init_asset_loader::<ActionSequenceAssetLoader>()
         .add_systems(OnEnter(Screen::Gameplay), load_action_sequence)
         .add_systems(Update, update_game_sequence.in_set(PausableSystems));
+
+    #[cfg(feature = "dev")]
+    app.add_observer(enter_mechanic)
+        .add_observer(retire_mechanic)
+        .add_observer(skip_to_action);
 }
 
-#[derive(States, Debug, Hash, Eq, PartialEq, Clone, Default, Copy, EnumString)]
+#[derive(
+    States,
+    Debug,
+    Hash,
+    Eq,
+    PartialEq,
+    Clone,
+    Default,
+    Copy,
+    Reflect,
+    EnumString,
+    EnumIter,
+    AsRefStr,
+)]
 #[strum(serialize_all = "title_case")]
 #[strum(ascii_case_insensitive)]
 pub enum GameMechanic {
@@ -33,27 +67,121 @@ pub enum GameMechanic {
     Pentagon,
 }
 
+impl GameMechanic {
+    /// Mechanics which have to be active for this one to work.
+    pub fn prerequisites(self) -> &'static [GameMechanic] {
+        match self {
+            Self::ButtonTime | Self::Durability | Self::Triangles | Self::Square => &[Self::Button],
+            Self::Fix => &[Self::Button, Self::Durability],
+            _ => &[],
+        }
+    }
+}
+
 enum ActionType {
-    ChangeText(String),
+    /// An empty text clears the guide.
+    ChangeText(GuideMessage),
     SpawnMechanic(GameMechanic),
+    Checkpoint(String),
+}
+
+/// When an action is invoked, relative to the previous action.
+#[derive(Clone, Copy, Debug)]
+enum ActionTime {
+    Seconds(f32),
+    /// On the given beat of the music, e.g. `4b` is on the fourth beat after the previous
+    /// action, so spawns land on the music.
+    Beats(f32),
+}
+
+impl std::fmt::Display for ActionTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Seconds(seconds) => write!(f, "{seconds}"),
+            Self::Beats(beats) => write!(f, "{beats}b"),
+        }
+    }
+}
+
+impl std::str::FromStr for ActionTime {
+    type Err = std::num::ParseFloatError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.strip_suffix('b') {
+            Some(beats) => beats.parse().map(Self::Beats),
+            None => text.parse().map(Self::Seconds),
+        }
+    }
 }
 
 struct Action {
-    /// in seconds
-    time: f32,
+    time: ActionTime,
     action_type: ActionType,
 }
 
 #[derive(Asset, TypePath)]
-struct ActionSequence(Vec<Action>);
+pub struct ActionSequence(Vec<Action>);
 
 #[derive(Resource, Default)]
-struct SequencerState {
+pub struct SequencerState {
     sequence: Handle<ActionSequence>,
     elapsed_time: f32,
+    /// Beat of the music the next action waits for, if it is timed in beats.
+    beat_target: Option<f32>,
+    action_index: usize,
+    /// Mechanics spawned so far, in order.
+    active_mechanics: Vec<GameMechanic>,
+    /// Mechanics to enter (one per frame) before the sequence continues.
+    mechanic_queue: VecDeque<GameMechanic>,
+    /// Whether the run starts from a checkpoint or in practice mode. The guide text is cleared
+    /// once all queued mechanics have been entered.
+    restoring: bool,
+    /// Elapsed time to restore once all queued mechanics have been entered.
+    restored_time: Option<f32>,
+}
+
+impl SequencerState {
+    /// Whether the sequence spawns the mechanic with an action timed in beats, so what it spawns
+    /// should land on the music too.
+    pub fn spawns_on_beat(
+        &self,
+        action_sequences: &Assets<ActionSequence>,
+        mechanic: GameMechanic,
+    ) -> bool {
+        action_sequences
+            .get(&self.sequence)
+            .is_some_and(|sequence| {
+                sequence.0.iter().any(|action| match action.action_type {
+                    ActionType::SpawnMechanic(spawned) => {
+                        spawned == mechanic && matches!(action.time, ActionTime::Beats(_))
+                    }
+                    _ => false,
+                })
+            })
+    }
+}
+
+/// Snapshot of a run taken when the sequencer reaches a checkpoint action.
+#[derive(Clone)]
+pub struct Checkpoint {
+    pub name: String,
     action_index: usize,
+    elapsed_time: Option<f32>,
+    mechanics: Vec<GameMechanic>,
 }
 
+/// The last checkpoint reached in the current run.
+#[derive(Resource, Default)]
+pub struct LastCheckpoint(pub Option<Checkpoint>);
+
+/// Whether the next run should start from [`LastCheckpoint`] instead of from the beginning.
+#[derive(Resource, Default)]
+struct RestoreCheckpoint(bool);
+
+/// Restarts the run from [`LastCheckpoint`].
+#[derive(Event)]
+pub struct RestartFromCheckpoint;
+
 #[derive(Default)]
 struct ActionSequenceAssetLoader;
 
@@ -61,6 +189,20 @@ struct ActionSequenceAssetLoader;
 pub enum ActionSequenceLoaderError {
     #[error("Could not load asset: {0}")]
     Io(#[from] std::io::Error),
+    #[error("Line {0}: expected `time | action type | content`.")]
+    InvalidFormat(usize),
+    #[error("Line {0}: unknown action type `{1}`, expected `T`, `M` or `C`.")]
+    InvalidActionType(usize, String),
+    #[error("Line {0}: unknown mechanic `{1}`.")]
+    InvalidMechanic(usize, String),
+    #[error("Line {0}: {1}.")]
+    InvalidMarkup(usize, MarkupError),
+    #[error("Line {0}: `{1}` is not a valid time, expected seconds or beats, e.g. `4` or `4b`.")]
+    InvalidTime(usize, String),
+    #[error("Line {0}: `{1}` is not a valid duration in seconds.")]
+    InvalidDuration(usize, String),
+    #[error("Line {0}: `{1}` is not a priority, expected `flavour`, `normal` or `urgent`.")]
+    InvalidPriority(usize, String),
 }
 
 impl AssetLoader for ActionSequenceAssetLoader {
@@ -79,18 +221,56 @@ impl AssetLoader for ActionSequenceAssetLoader {
 
         let actions = text
             .lines()
-            .map(|l| l.trim())
-            .filter(|l| !(l.is_empty() || l.starts_with("#")))
-            .map(|l| l.split('|').map(|t| t.trim()).collect_tuple().unwrap())
-            .map(|(time, action_type, content)| Action {
-                time: time.parse().unwrap(),
-                action_type: match action_type {
-                    "T" => ActionType::ChangeText(content.into()),
-                    "M" => ActionType::SpawnMechanic(content.parse::<GameMechanic>().unwrap()),
-                    _ => panic!("Invalid action type."),
-                },
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !(l.is_empty() || l.starts_with("#")))
+            .map(|(line, l)| (line, l.split('|').map(|t| t.trim()).collect_vec()))
+            .map(|(line, parts)| {
+                let [time, action_type, content, ..] = parts[..] else {
+                    return Err(ActionSequenceLoaderError::InvalidFormat(line));
+                };
+                let action_type = match (action_type, content) {
+                    ("T", content) => {
+                        markup::parse(content).map_err(|error| {
+                            ActionSequenceLoaderError::InvalidMarkup(line, error)
+                        })?;
+                        // optional `| duration | priority` columns
+                        let mut message = GuideMessage::new(content);
+                        if let Some(duration) = parts.get(3).filter(|d| !d.is_empty()) {
+                            message = message.with_duration(duration.parse().map_err(|_| {
+                                ActionSequenceLoaderError::InvalidDuration(
+                                    line,
+                                    duration.to_string(),
+                                )
+                            })?);
+                        }
+                        if let Some(priority) = parts.get(4) {
+                            message = message.with_priority(priority.parse().map_err(|_| {
+                                ActionSequenceLoaderError::InvalidPriority(
+                                    line,
+                                    priority.to_string(),
+                                )
+                            })?);
+                        }
+                        ActionType::ChangeText(message)
+                    }
+                    ("M", content) => ActionType::SpawnMechanic(content.parse().map_err(|_| {
+                        ActionSequenceLoaderError::InvalidMechanic(line, content.into())
+                    })?),
+                    ("C", content) => ActionType::Checkpoint(content.into()),
+                    (action_type, _) => {
+                        return Err(ActionSequenceLoaderError::InvalidActionType(
+                            line,
+                            action_type.into(),
+                        ));
+                    }
+                };
+                let time = time
+                    .parse()
+                    .map_err(|_| ActionSequenceLoaderError::InvalidTime(line, time.into()))?;
+                Ok::<_, ActionSequenceLoaderError>(Action { time, action_type })
             })
-            .collect_vec();
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(ActionSequence(actions))
     }
@@ -100,22 +280,260 @@ impl AssetLoader for ActionSequenceAssetLoader {
     }
 }
 
-fn load_action_sequence(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// Marks the entities a mechanic spawns, so they can be despawned when it's retired.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MechanicEntity(pub GameMechanic);
+
+/// Enters a mechanic outside of the action sequence. Used by the dev tools.
+#[cfg(feature = "dev")]
+#[derive(Event)]
+pub struct EnterMechanic(pub GameMechanic);
+
+/// Despawns everything a mechanic has spawned. Used by the dev tools.
+#[cfg(feature = "dev")]
+#[derive(Event)]
+pub struct RetireMechanic(pub GameMechanic);
+
+/// Jumps forward in the action sequence, invoking all skipped actions. Used by the dev tools.
+#[cfg(feature = "dev")]
+#[derive(Event)]
+pub struct SkipToAction(pub usize);
+
+/// Snapshot of the sequencer shown by the dev tools.
+#[cfg(feature = "dev")]
+pub struct SequencerInfo {
+    pub action_index: usize,
+    /// Seconds until the next action is invoked.
+    pub next_action_in: Option<f32>,
+    /// Delay and description of every action in the sequence.
+    pub actions: Vec<(String, String)>,
+    pub active_mechanics: Vec<GameMechanic>,
+}
+
+#[cfg(feature = "dev")]
+impl SequencerState {
+    pub fn info(
+        &self,
+        action_sequences: &Assets<ActionSequence>,
+        beat_clock: Option<&BeatClock>,
+    ) -> SequencerInfo {
+        let sequence = action_sequences.get(&self.sequence);
+
+        SequencerInfo {
+            action_index: self.action_index,
+            next_action_in: sequence
+                .and_then(|sequence| sequence.0.get(self.action_index))
+                .and_then(|action| match action.time {
+                    ActionTime::Seconds(seconds) => Some(seconds - self.elapsed_time),
+                    ActionTime::Beats(_) => self
+                        .beat_target
+                        .zip(beat_clock)
+                        .map(|(target, clock)| clock.time_of(target) - clock.elapsed()),
+                }),
+            actions: sequence
+                .map(|sequence| {
+                    sequence
+                        .0
+                        .iter()
+                        .map(|action| (action.time.to_string(), action.action_type.describe()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            active_mechanics: self.active_mechanics.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "dev")]
+impl ActionType {
+    fn describe(&self) -> String {
+        match self {
+            Self::ChangeText(message) => format!("T | {}", message.text),
+            Self::SpawnMechanic(mechanic) => format!("M | {}", mechanic.as_ref()),
+            Self::Checkpoint(name) => format!("C | {name}"),
+        }
+    }
+}
+
+fn load_action_sequence(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut last_checkpoint: ResMut<LastCheckpoint>,
+    mut restore: ResMut<RestoreCheckpoint>,
+    practice: Res<PracticeRun>,
+) {
+    // practice runs only enter the mechanic and its prerequisites, without any sequence
+    if let Some(mechanic) = practice.0 {
+        last_checkpoint.0 = None;
+        commands.insert_resource(SequencerState {
+            mechanic_queue: mechanic
+                .prerequisites()
+                .iter()
+                .copied()
+                .chain([mechanic])
+                .collect(),
+            restoring: true,
+            ..default()
+        });
+        return;
+    }
+
+    let sequence = asset_server.load("sequence.seq");
+
+    let checkpoint = match restore.0 {
+        true => last_checkpoint.0.clone(),
+        false => None,
+    };
+    restore.0 = false;
+
+    let Some(checkpoint) = checkpoint else {
+        last_checkpoint.0 = None;
+        commands.insert_resource(SequencerState {
+            sequence,
+            ..default()
+        });
+        return;
+    };
+
     commands.insert_resource(SequencerState {
-        sequence: asset_server.load("sequence.seq"),
+        sequence,
+        action_index: checkpoint.action_index,
+        active_mechanics: checkpoint.mechanics.clone(),
+        mechanic_queue: checkpoint.mechanics.into(),
+        restoring: true,
+        restored_time: checkpoint.elapsed_time,
         ..default()
     });
 }
 
+fn restart_from_checkpoint(
+    _: Trigger<RestartFromCheckpoint>,
+    mut restore: ResMut<RestoreCheckpoint>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    restore.0 = true;
+    next_screen.set(Screen::Loading);
+}
+
+#[cfg(feature = "dev")]
+fn enter_mechanic(trigger: Trigger<EnterMechanic>, mut state: ResMut<SequencerState>) {
+    let mechanic = trigger.event().0;
+    state.mechanic_queue.push_back(mechanic);
+    state.active_mechanics.push(mechanic);
+}
+
+#[cfg(feature = "dev")]
+fn retire_mechanic(
+    trigger: Trigger<RetireMechanic>,
+    mut commands: Commands,
+    mut state: ResMut<SequencerState>,
+    entities: Query<(Entity, &MechanicEntity)>,
+) {
+    let retired = trigger.event().0;
+    state
+        .active_mechanics
+        .retain(|mechanic| *mechanic != retired);
+    for (entity, mechanic) in &entities {
+        if mechanic.0 == retired {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(feature = "dev")]
+fn skip_to_action(
+    trigger: Trigger<SkipToAction>,
+    mut state: ResMut<SequencerState>,
+    mut commands: Commands,
+    mut last_checkpoint: ResMut<LastCheckpoint>,
+    elapsed_time: Option<ResMut<ElapsedTime>>,
+    action_sequences: Res<Assets<ActionSequence>>,
+    beat_clock: Option<Res<BeatClock>>,
+) {
+    let Some(action_sequence) = action_sequences.get(&state.sequence) else {
+        return;
+    };
+
+    let start = state.action_index;
+    let end = trigger.event().0.min(action_sequence.0.len());
+    if end <= start {
+        return;
+    }
+
+    // what the timer would show, once it has been spawned
+    let mut timer_time = elapsed_time.as_ref().map(|elapsed_time| elapsed_time.0);
+    // the time already waited for the next action
+    let mut waited = state.elapsed_time;
+    for (index, action) in action_sequence.0.iter().enumerate().take(end).skip(start) {
+        let delay = match action.time {
+            ActionTime::Seconds(seconds) => seconds,
+            // roughly, the beats would be counted from when the previous action was invoked
+            ActionTime::Beats(beats) => {
+                beats
+                    * beat_clock
+                        .as_ref()
+                        .map_or(0.0, |clock| clock.beat_duration())
+            }
+        };
+        if let Some(timer_time) = &mut timer_time {
+            *timer_time += delay - waited;
+        }
+        waited = 0.0;
+
+        match &action.action_type {
+            ActionType::ChangeText(message) => show_guide_message(&mut commands, message),
+            ActionType::SpawnMechanic(mechanic) => {
+                state.mechanic_queue.push_back(*mechanic);
+                state.active_mechanics.push(*mechanic);
+                if *mechanic == GameMechanic::Timer {
+                    timer_time.get_or_insert(0.0);
+                }
+            }
+            ActionType::Checkpoint(name) => {
+                last_checkpoint.0 = Some(Checkpoint {
+                    name: name.clone(),
+                    action_index: index + 1,
+                    elapsed_time: timer_time,
+                    mechanics: state.active_mechanics.clone(),
+                });
+            }
+        }
+    }
+
+    state.action_index = end;
+    state.elapsed_time = 0.0;
+    state.beat_target = None;
+    match elapsed_time {
+        Some(mut elapsed_time) => elapsed_time.0 = timer_time.unwrap_or_default(),
+        // the timer is spawned with the skipped mechanics, its time is set once they are entered
+        None => state.restored_time = timer_time,
+    }
+}
+
 fn update_game_sequence(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut guide_text: Single<&mut Text, With<GuideText>>,
     mut state: ResMut<SequencerState>,
     mut spawn_mechanic: ResMut<NextState<GameMechanic>>,
+    mut last_checkpoint: ResMut<LastCheckpoint>,
+    elapsed_time: Option<Res<ElapsedTime>>,
     action_sequences: Res<Assets<ActionSequence>>,
+    beat_clock: Option<Res<BeatClock>>,
     time: Res<Time>,
 ) {
+    // re-enter mechanics of a restored checkpoint, only one state transition can happen per frame
+    if let Some(mechanic) = state.mechanic_queue.pop_front() {
+        spawn_mechanic.set(mechanic);
+        return;
+    }
+    if let Some(restored_time) = state.restored_time.take() {
+        commands.insert_resource(ElapsedTime(restored_time));
+    }
+    if state.restoring {
+        state.restoring = false;
+        commands.trigger(ClearGuide);
+    }
+
     // get sequence
     let Some(action_sequence) = action_sequences.get(&state.sequence) else {
         return;
@@ -129,22 +547,53 @@ fn update_game_sequence(
 
     // update time
     state.elapsed_time += time.delta_secs();
-    if state.elapsed_time < action.time {
-        return;
+    match action.time {
+        ActionTime::Seconds(seconds) => {
+            if state.elapsed_time < seconds {
+                return;
+            }
+            state.elapsed_time -= seconds;
+        }
+        ActionTime::Beats(beats) => {
+            // waits for the music to start
+            let Some(beat_clock) = beat_clock else {
+                return;
+            };
+            // beats are counted from the last beat before the action became the next one
+            let target = *state
+                .beat_target
+                .get_or_insert(beat_clock.position().floor() + beats);
+            if beat_clock.position() < target {
+                return;
+            }
+            state.elapsed_time = 0.0;
+            state.beat_target = None;
+        }
     }
-    state.elapsed_time -= action.time;
     state.action_index += 1;
 
     // invoke action
     match &action.action_type {
-        ActionType::ChangeText(text) => {
-            guide_text.0 = text.into();
-
-            if !guide_text.0.is_empty() {
-                let handle = asset_server.load("audio/sound_effects/new_text.ogg");
-                commands.spawn((Name::new("New text sound"), sound_effect(handle, 0.2)));
-            }
+        ActionType::ChangeText(message) => show_guide_message(&mut commands, message),
+        ActionType::SpawnMechanic(mechanic) => {
+            spawn_mechanic.set(*mechanic);
+            state.active_mechanics.push(*mechanic);
+        }
+        ActionType::Checkpoint(name) => {
+            last_checkpoint.0 = Some(Checkpoint {
+                name: name.clone(),
+                action_index: state.action_index,
+                elapsed_time: elapsed_time.map(|elapsed_time| elapsed_time.0),
+                mechanics: state.active_mechanics.clone(),
+            });
         }
-        ActionType::SpawnMechanic(mechanic) => spawn_mechanic.set(*mechanic),
+    }
+}
+
+fn show_guide_message(commands: &mut Commands, message: &GuideMessage) {
+    if message.text.is_empty() {
+        commands.trigger(ClearGuide);
+    } else {
+        commands.trigger(message.clone());
     }
 }
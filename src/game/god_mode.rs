@@ -0,0 +1,96 @@
+//! Development-only invulnerability. While enabled, game overs are counted instead of ending the run.
+
+use std::collections::HashMap;
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+
+use crate::{game::game_sequencer::GameMechanic, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GodMode>()
+        .add_systems(OnEnter(Screen::Gameplay), spawn_death_counter)
+        .add_systems(
+            Update,
+            (
+                toggle_god_mode.run_if(input_just_pressed(TOGGLE_KEY)),
+                update_death_counter.run_if(resource_changed::<GodMode>),
+            )
+                .chain()
+                .run_if(in_state(Screen::Gameplay)),
+        );
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::F2;
+/// Real-time seconds during which repeated game overs from the same cause count as one death.
+/// Most causes keep firing every frame until the culprit is gone.
+const DEATH_DEBOUNCE: f32 = 1.0;
+
+#[derive(Resource, Default)]
+pub struct GodMode {
+    pub enabled: bool,
+    deaths: u32,
+    last_deaths: HashMap<GameMechanic, f32>,
+}
+
+impl GodMode {
+    /// Returns whether the game over should be swallowed.
+    pub fn absorb(&mut self, cause: GameMechanic, now: f32) -> bool {
+        if !self.enabled || cause == GameMechanic::Victory {
+            return false;
+        }
+
+        let last_death = self.last_deaths.insert(cause, now);
+        if last_death.is_none_or(|last_death| now - last_death > DEATH_DEBOUNCE) {
+            self.deaths += 1;
+            info!("God mode absorbed a game over caused by {cause:?}");
+        }
+
+        true
+    }
+}
+
+#[derive(Component)]
+struct DeathCounter;
+
+fn spawn_death_counter(mut commands: Commands, mut god_mode: ResMut<GodMode>) {
+    god_mode.deaths = 0;
+    god_mode.last_deaths.clear();
+
+    commands.spawn((
+        Name::new("Death counter"),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Px(20.0),
+            top: Px(20.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font_size: 24.0,
+            ..default()
+        },
+        TextColor(Color::linear_rgb(1.0, 0.2, 0.2)),
+        GlobalZIndex(3),
+        DeathCounter,
+        StateScoped(Screen::Gameplay),
+        Pickable::IGNORE,
+    ));
+}
+
+fn toggle_god_mode(mut god_mode: ResMut<GodMode>) {
+    god_mode.enabled = !god_mode.enabled;
+    info!("God mode {}", if god_mode.enabled { "on" } else { "off" });
+}
+
+fn update_death_counter(
+    god_mode: Res<GodMode>,
+    mut counter: Single<(&mut Text, &mut Visibility), With<DeathCounter>>,
+) {
+    let (text, visibility) = &mut *counter;
+    text.0 = format!("God mode - deaths: {}", god_mode.deaths);
+    **visibility = if god_mode.enabled {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+}
@@ -1,15 +1,163 @@
+use std::collections::VecDeque;
+
 use bevy::prelude::*;
+use strum_macros::EnumString;
 
-use crate::screens::Screen;
+use crate::{
+    PausableSystems,
+    audio::sfx::PlaySfx,
+    localization::{Language, Localization},
+    screens::Screen,
+    theme::markup::Markup,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_guide);
+    app.init_resource::<GuideQueue>()
+        .init_resource::<GuideHistory>()
+        .add_systems(OnEnter(Screen::Gameplay), (reset_guide, spawn_guide))
+        .add_systems(
+            Update,
+            (show_next_message, reveal_message)
+                .chain()
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        )
+        .add_systems(
+            Update,
+            translate_current_message.run_if(resource_changed::<Language>),
+        )
+        .add_observer(queue_message)
+        .add_observer(clear_guide);
 }
 
+/// Seconds between two words of the typewriter reveal.
+const WORD_INTERVAL: f32 = 0.08;
+
 #[derive(Component)]
 pub struct GuideText;
 
-fn spawn_guide(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[strum(ascii_case_insensitive)]
+pub enum GuidePriority {
+    Flavour,
+    #[default]
+    Normal,
+    /// Pre-empts less important messages, which are shown again afterwards.
+    Urgent,
+}
+
+/// Queues a message for the guide text. The text can be a localization key and can contain
+/// markup.
+#[derive(Event, Clone)]
+pub struct GuideMessage {
+    pub text: String,
+    /// Seconds the message stays once it is fully revealed. Without a duration, the message stays
+    /// until the next one replaces it.
+    pub duration: Option<f32>,
+    pub priority: GuidePriority,
+}
+
+impl GuideMessage {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            duration: None,
+            priority: GuidePriority::default(),
+        }
+    }
+
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    pub fn with_priority(mut self, priority: GuidePriority) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// Clears the guide text and drops all queued messages, except urgent ones.
+#[derive(Event)]
+pub struct ClearGuide;
+
+/// Every message queued during the current run, oldest first.
+#[derive(Resource, Default)]
+pub struct GuideHistory(pub Vec<String>);
+
+#[derive(Resource, Default)]
+struct GuideQueue {
+    current: Option<ShownMessage>,
+    /// Ordered by priority, then by the time they were queued.
+    queue: VecDeque<GuideMessage>,
+}
+
+impl GuideQueue {
+    /// Inserts the message behind all messages with the same or higher priority.
+    fn push(&mut self, message: GuideMessage) {
+        let index = self
+            .queue
+            .iter()
+            .position(|queued| queued.priority < message.priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(index, message);
+    }
+
+    /// Inserts the message in front of all messages with the same priority.
+    fn push_front(&mut self, message: GuideMessage) {
+        let index = self
+            .queue
+            .iter()
+            .position(|queued| queued.priority <= message.priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(index, message);
+    }
+}
+
+struct ShownMessage {
+    message: GuideMessage,
+    /// The translated text.
+    text: String,
+    /// Number of characters up to the end of each word, without markup.
+    word_ends: Vec<usize>,
+    revealed_words: usize,
+    word_timer: Timer,
+    /// Ticks once the message is fully revealed.
+    display_timer: Option<Timer>,
+}
+
+impl ShownMessage {
+    fn new(message: GuideMessage, localization: &Localization) -> Self {
+        let text = localization.translate(&message.text);
+        let word_ends = Markup::new(&text)
+            .plain_text()
+            .split_inclusive(char::is_whitespace)
+            .scan(0, |end, word| {
+                *end += word.chars().count();
+                Some(*end)
+            })
+            .collect();
+
+        Self {
+            word_ends,
+            display_timer: message
+                .duration
+                .map(|duration| Timer::from_seconds(duration, TimerMode::Once)),
+            message,
+            text,
+            revealed_words: 0,
+            word_timer: Timer::from_seconds(WORD_INTERVAL, TimerMode::Repeating),
+        }
+    }
+}
+
+fn reset_guide(mut queue: ResMut<GuideQueue>, mut history: ResMut<GuideHistory>) {
+    *queue = GuideQueue::default();
+    history.0.clear();
+}
+
+fn spawn_guide(mut commands: Commands) {
     commands
         .spawn((
             Node {
@@ -27,9 +175,8 @@ fn spawn_guide(mut commands: Commands, asset_server: Res<AssetServer>) {
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text::new("You're the red dot. Move with your mouse."),
+                Markup::default(),
                 TextFont {
-                    font: asset_server.load("fonts/Super Vanilla.ttf"),
                     font_size: 40.0,
                     ..default()
                 },
@@ -48,3 +195,103 @@ fn spawn_guide(mut commands: Commands, asset_server: Res<AssetServer>) {
             ));
         });
 }
+
+fn queue_message(
+    trigger: Trigger<GuideMessage>,
+    mut queue: ResMut<GuideQueue>,
+    mut history: ResMut<GuideHistory>,
+) {
+    let message = trigger.event().clone();
+    history.0.push(message.text.clone());
+    queue.push(message);
+}
+
+fn clear_guide(
+    _: Trigger<ClearGuide>,
+    mut queue: ResMut<GuideQueue>,
+    mut guide_text: Single<&mut Markup, With<GuideText>>,
+) {
+    queue
+        .queue
+        .retain(|message| message.priority == GuidePriority::Urgent);
+
+    if queue
+        .current
+        .as_ref()
+        .is_some_and(|current| current.message.priority < GuidePriority::Urgent)
+    {
+        queue.current = None;
+        **guide_text = Markup::default();
+    }
+}
+
+fn show_next_message(mut queue: ResMut<GuideQueue>, localization: Localization) {
+    let Some(next) = queue.queue.front() else {
+        return;
+    };
+
+    match &queue.current {
+        None => {}
+        // pre-empted messages are shown again afterwards
+        Some(current) if next.priority > current.message.priority => {
+            let current = queue.current.take().unwrap();
+            let next = queue.queue.pop_front().unwrap();
+            queue.push_front(current.message);
+            queue.current = Some(ShownMessage::new(next, &localization));
+            return;
+        }
+        // messages without a duration stay only until the next one arrives
+        Some(current) if current.display_timer.is_none() => {}
+        Some(_) => return,
+    }
+
+    let next = queue.queue.pop_front().unwrap();
+    queue.current = Some(ShownMessage::new(next, &localization));
+}
+
+fn reveal_message(
+    mut commands: Commands,
+    mut queue: ResMut<GuideQueue>,
+    mut guide_text: Single<&mut Markup, With<GuideText>>,
+    time: Res<Time>,
+) {
+    let Some(current) = &mut queue.current else {
+        return;
+    };
+
+    // typewriter reveal, one word at a time
+    let word_count = current.word_ends.len();
+    if current.revealed_words < word_count {
+        let words = if current.revealed_words == 0 {
+            **guide_text = Markup::new(&current.text);
+            1
+        } else {
+            current.word_timer.tick(time.delta());
+            current.word_timer.times_finished_this_tick() as usize
+        };
+        if words == 0 {
+            return;
+        }
+
+        current.revealed_words = (current.revealed_words + words).min(word_count);
+        guide_text.reveal(Some(current.word_ends[current.revealed_words - 1]));
+
+        commands.trigger(PlaySfx("guide_blip"));
+        return;
+    }
+
+    let Some(display_timer) = &mut current.display_timer else {
+        return;
+    };
+    if display_timer.tick(time.delta()).finished() {
+        queue.current = None;
+        **guide_text = Markup::default();
+    }
+}
+
+/// Reveals the current message again in the new language.
+fn translate_current_message(mut queue: ResMut<GuideQueue>, localization: Localization) {
+    if let Some(current) = queue.current.take() {
+        queue.current = Some(ShownMessage::new(current.message, &localization));
+    }
+}
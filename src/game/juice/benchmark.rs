@@ -0,0 +1,101 @@
+//! Development-only benchmark of the juice effects. Press F3 to simulate clicks at increasing
+//! rates, the asset counts after each rate are logged. They should stop growing once the
+//! [`ParticlePool`] is full.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use rand::Rng;
+
+use crate::{
+    game::{
+        juice::{circles::SpawnCircles, particles::ParticlePool},
+        player::CLICK_PARTICLES_Z,
+    },
+    playfield::PLAYFIELD_SIZE,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ParticleBenchmark>().add_systems(
+        Update,
+        (
+            toggle_benchmark.run_if(input_just_pressed(TOGGLE_KEY)),
+            run_benchmark,
+        )
+            .chain(),
+    );
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+/// Simulated clicks per second, each for [`STEP_DURATION`].
+const CLICK_RATES: [f32; 5] = [10.0, 50.0, 200.0, 1000.0, 5000.0];
+/// Real-time seconds each click rate is simulated for.
+const STEP_DURATION: f32 = 3.0;
+
+#[derive(Resource, Default)]
+struct ParticleBenchmark {
+    /// Index of the click rate being simulated, `None` while the benchmark isn't running.
+    step: Option<usize>,
+    elapsed: f32,
+    /// Clicks due but not simulated yet.
+    pending_clicks: f32,
+}
+
+fn toggle_benchmark(mut benchmark: ResMut<ParticleBenchmark>) {
+    *benchmark = ParticleBenchmark {
+        step: benchmark.step.is_none().then_some(0),
+        ..default()
+    };
+    info!(
+        "Particle benchmark {}",
+        if benchmark.step.is_some() {
+            "started"
+        } else {
+            "stopped"
+        }
+    );
+}
+
+fn run_benchmark(
+    mut commands: Commands,
+    mut benchmark: ResMut<ParticleBenchmark>,
+    pool: Res<ParticlePool>,
+    meshes: Res<Assets<Mesh>>,
+    materials: Res<Assets<ColorMaterial>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(step) = benchmark.step else {
+        return;
+    };
+    let rate = CLICK_RATES[step];
+
+    let mut rng = rand::rng();
+    benchmark.pending_clicks += rate * time.delta_secs();
+    while benchmark.pending_clicks >= 1.0 {
+        benchmark.pending_clicks -= 1.0;
+        let location = Vec2::new(
+            rng.random_range(-0.5..0.5) * PLAYFIELD_SIZE.x,
+            rng.random_range(-0.5..0.5) * PLAYFIELD_SIZE.y,
+        );
+        // different thicknesses, like the different click effects
+        commands.trigger(SpawnCircles {
+            location: location.extend(CLICK_PARTICLES_Z),
+            thickness: rng.random_range(2.0..6.0),
+            ..default()
+        });
+    }
+
+    benchmark.elapsed += time.delta_secs();
+    if benchmark.elapsed < STEP_DURATION {
+        return;
+    }
+    info!(
+        "Particle benchmark: {rate} clicks/s, {} meshes, {} color materials, {} pooled particles",
+        meshes.len(),
+        materials.len(),
+        pool.size(),
+    );
+    benchmark.elapsed = 0.0;
+    benchmark.step = Some(step + 1).filter(|step| *step < CLICK_RATES.len());
+    if benchmark.step.is_none() {
+        info!("Particle benchmark finished");
+    }
+}
@@ -1,10 +1,12 @@
+//! Expanding rings, e.g. where the player clicks. They are emitted as
+//! [`particles`](super::particles), one ring each.
+
 use bevy::prelude::*;
 
-use crate::PausableSystems;
+use crate::game::juice::particles::{ParticleEmitter, ParticleShape, particle_burst};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(Update, update_circles.in_set(PausableSystems))
-        .add_observer(spawn_circles);
+    app.add_observer(spawn_circles);
 }
 
 #[derive(Event)]
@@ -34,83 +36,33 @@ impl Default for SpawnCircles {
     }
 }
 
-#[derive(Component)]
-struct CircleParticle {
-    ttl_timer: Timer,
-    start_size: f32,
-    end_size: f32,
-    start_color: LinearRgba,
-    end_color: LinearRgba,
+/// A single ring growing from `start_size` to `end_size` while fading to the end color.
+fn ring_emitter(event: &SpawnCircles, start_size: f32, end_size: f32) -> ParticleEmitter {
+    ParticleEmitter {
+        shape: ParticleShape::Ring(1.0 - event.thickness / start_size),
+        lifetime: event.ttl..event.ttl,
+        size_over_life: vec![start_size, end_size],
+        color_over_life: vec![event.start_color, event.end_color],
+        ..default()
+    }
 }
 
-fn spawn_circles(
-    trigger: Trigger<SpawnCircles>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-) {
+fn spawn_circles(trigger: Trigger<SpawnCircles>, mut commands: Commands) {
     let event = trigger.event();
 
     // outer circle
-    commands.spawn((
-        Mesh2d(meshes.add(Annulus::new(1.0 - event.thickness / event.start_size, 1.0))),
-        MeshMaterial2d(materials.add(Color::LinearRgba(event.start_color))),
-        Transform::from_translation(event.location)
-            .with_scale(Vec2::splat(event.start_size).extend(1.0)),
-        CircleParticle {
-            ttl_timer: Timer::from_seconds(event.ttl, TimerMode::Once),
-            start_size: event.start_size,
-            end_size: event.end_size,
-            start_color: event.start_color,
-            end_color: event.end_color,
-        },
+    commands.spawn(particle_burst(
+        event.location,
+        ring_emitter(event, event.start_size, event.end_size),
     ));
 
-    // outer circle
-    let start_size = event.start_size - event.spacing;
-    let end_size = event.end_size - event.spacing;
-    commands.spawn((
-        Mesh2d(meshes.add(Annulus::new(1.0 - event.thickness / start_size, 1.0))),
-        MeshMaterial2d(materials.add(Color::LinearRgba(event.start_color))),
-        Transform::from_translation(event.location).with_scale(Vec2::splat(start_size).extend(1.0)),
-        CircleParticle {
-            ttl_timer: Timer::from_seconds(event.ttl, TimerMode::Once),
-            start_size,
-            end_size,
-            start_color: event.start_color,
-            end_color: event.end_color,
-        },
+    // inner circle
+    commands.spawn(particle_burst(
+        event.location,
+        ring_emitter(
+            event,
+            event.start_size - event.spacing,
+            event.end_size - event.spacing,
+        ),
     ));
 }
-
-fn update_circles(
-    mut commands: Commands,
-    mut query: Query<(
-        Entity,
-        &mut Transform,
-        &mut MeshMaterial2d<ColorMaterial>,
-        &mut CircleParticle,
-    )>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
-    time: Res<Time>,
-) {
-    for (entity, mut transform, mut material, mut particle) in query.iter_mut() {
-        particle.ttl_timer.tick(time.delta());
-        let t = particle.ttl_timer.fraction();
-        if particle.ttl_timer.finished() {
-            commands.entity(entity).despawn();
-            continue;
-        }
-
-        // update scale
-        let scale = particle.start_size.lerp(particle.end_size, t);
-        transform.scale = Vec2::splat(scale).extend(transform.scale.z);
-
-        // update color
-        let color = particle
-            .start_color
-            .to_vec4()
-            .lerp(particle.end_color.to_vec4(), t);
-        material.0 = materials.add(Color::linear_rgba(color.x, color.y, color.z, color.w));
-    }
-}
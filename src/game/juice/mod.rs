@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 
+#[cfg(feature = "dev")]
+mod benchmark;
 pub mod circles;
+pub mod particles;
 pub mod pulse_effect;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((pulse_effect::plugin, circles::plugin));
+    app.add_plugins((pulse_effect::plugin, particles::plugin, circles::plugin));
+
+    #[cfg(feature = "dev")]
+    app.add_plugins(benchmark::plugin);
 }
@@ -0,0 +1,383 @@
+//! Particles, emitted by [`ParticleEmitter`]s. They are drawn by a fixed pool of entities which
+//! are reused, so effects don't create new entities or assets once the pool is full.
+//!
+//! Emitters can be added to any entity, or spawned on their own with [`particle_burst`].
+
+use std::{collections::HashMap, f32::consts::TAU, ops::Range};
+
+use bevy::{color::Mix, prelude::*, sprite::AlphaMode2d, transform::helper::TransformHelper};
+use rand::Rng;
+
+use crate::PausableSystems;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ParticlePool>().add_systems(
+        Update,
+        (emit_particles, update_particles)
+            .chain()
+            .in_set(PausableSystems),
+    );
+}
+
+/// Particles drawn at the same time, the oldest particle is reused for a new one once all are
+/// in use.
+const POOL_SIZE: usize = 256;
+/// Inner radii of shared ring meshes are rounded to this many steps.
+const RING_STEPS: f32 = 100.0;
+/// Color channels of shared materials are rounded to this many steps, so fading particles
+/// switch between a few materials instead of changing theirs every frame.
+const COLOR_STEPS: f32 = 32.0;
+
+#[derive(Clone, Copy, Debug)]
+pub enum EmitMode {
+    /// Emits this many particles at once, then the emitter is removed.
+    Burst(usize),
+    /// Emits this many particles per second.
+    Continuous(f32),
+}
+
+/// Shape of the particles, scaled by their size.
+#[derive(Clone, Debug)]
+pub enum ParticleShape {
+    /// A right triangle with legs of 1.
+    Triangle,
+    /// A circle with a radius of 1.
+    Circle,
+    /// A ring with an outer radius of 1 and the given inner radius.
+    Ring(f32),
+    Mesh(Handle<Mesh>),
+}
+
+/// Emits particles from the entity's position. Ranges are sampled for each particle.
+#[derive(Component, Clone, Debug)]
+#[require(PendingParticles)]
+pub struct ParticleEmitter {
+    pub mode: EmitMode,
+    pub shape: ParticleShape,
+    /// Particles start at a random point within this distance of the emitter.
+    pub spawn_radius: f32,
+    /// Speed away from the emitter. Particles emitted from its center fly in a random direction.
+    pub speed: Range<f32>,
+    pub gravity: Vec2,
+    /// How quickly particles slow down, their velocity decays by `e^-drag` each second.
+    pub drag: f32,
+    /// Starting rotation in radians.
+    pub rotation: Range<f32>,
+    /// Radians per second.
+    pub angular_velocity: Range<f32>,
+    /// Seconds each particle lives.
+    pub lifetime: Range<f32>,
+    /// Sizes the particles go through from birth to death, evenly spaced over their life.
+    pub size_over_life: Vec<f32>,
+    /// Colors the particles go through from birth to death, evenly spaced over their life.
+    pub color_over_life: Vec<LinearRgba>,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            mode: EmitMode::Burst(1),
+            shape: ParticleShape::Circle,
+            spawn_radius: 0.0,
+            speed: 0.0..0.0,
+            gravity: Vec2::ZERO,
+            drag: 0.0,
+            rotation: 0.0..0.0,
+            angular_velocity: 0.0..0.0,
+            lifetime: 1.0..1.0,
+            size_over_life: vec![1.0],
+            color_over_life: vec![LinearRgba::WHITE],
+        }
+    }
+}
+
+/// A standalone emitter at the given location, it despawns once its burst is emitted.
+pub fn particle_burst(location: Vec3, emitter: ParticleEmitter) -> impl Bundle {
+    (
+        Name::new("Particle burst"),
+        Transform::from_translation(location),
+        emitter,
+        DespawnAfterBurst,
+    )
+}
+
+#[derive(Component)]
+struct DespawnAfterBurst;
+
+/// Particles a continuous emitter owes, it emits them once they add up to whole particles.
+#[derive(Component, Default)]
+struct PendingParticles(f32);
+
+/// The pooled particle entities, and the meshes and materials shared between them.
+#[derive(Resource, Default)]
+pub struct ParticlePool {
+    particles: Vec<Entity>,
+    /// Index of the next particle to reuse, the oldest one once the pool is full.
+    next: usize,
+    meshes: HashMap<MeshKey, Handle<Mesh>>,
+    /// Materials by their rounded color channels.
+    materials: HashMap<[u8; 4], Handle<ColorMaterial>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum MeshKey {
+    Triangle,
+    Circle,
+    /// A ring by its rounded inner radius.
+    Ring(u32),
+}
+
+impl ParticlePool {
+    /// Particle entities in the pool, drawn or waiting to be reused.
+    #[cfg(feature = "dev")]
+    pub fn size(&self) -> usize {
+        self.particles.len()
+    }
+
+    fn mesh(&mut self, shape: &ParticleShape, meshes: &mut Assets<Mesh>) -> Handle<Mesh> {
+        let key = match shape {
+            ParticleShape::Triangle => MeshKey::Triangle,
+            ParticleShape::Circle => MeshKey::Circle,
+            ParticleShape::Ring(inner_radius) => {
+                MeshKey::Ring((inner_radius.clamp(0.0, 1.0) * RING_STEPS).round() as u32)
+            }
+            ParticleShape::Mesh(mesh) => return mesh.clone(),
+        };
+        self.meshes
+            .entry(key)
+            .or_insert_with(|| {
+                meshes.add(match key {
+                    MeshKey::Triangle => Mesh::from(Triangle2d::new(
+                        vec2(-0.5, -0.5),
+                        vec2(0.5, -0.5),
+                        vec2(-0.5, 0.5),
+                    )),
+                    MeshKey::Circle => Circle::new(1.0).into(),
+                    MeshKey::Ring(inner_radius) => {
+                        Annulus::new(inner_radius as f32 / RING_STEPS, 1.0).into()
+                    }
+                })
+            })
+            .clone()
+    }
+
+    /// The shared material closest to the color.
+    fn material(
+        &mut self,
+        color: LinearRgba,
+        materials: &mut Assets<ColorMaterial>,
+    ) -> Handle<ColorMaterial> {
+        let key = color
+            .to_f32_array()
+            .map(|channel| (channel.clamp(0.0, 1.0) * COLOR_STEPS).round() as u8);
+        self.materials
+            .entry(key)
+            .or_insert_with(|| {
+                let [red, green, blue, alpha] = key.map(|channel| channel as f32 / COLOR_STEPS);
+                // blended even if the color is opaque, so particles can fade
+                materials.add(ColorMaterial {
+                    color: Color::linear_rgba(red, green, blue, alpha),
+                    alpha_mode: AlphaMode2d::Blend,
+                    ..default()
+                })
+            })
+            .clone()
+    }
+}
+
+/// Marks the entities of the [`ParticlePool`].
+#[derive(Component)]
+struct PooledParticle;
+
+#[derive(Component)]
+struct Particle {
+    age: Timer,
+    velocity: Vec2,
+    angular_velocity: f32,
+    gravity: Vec2,
+    drag: f32,
+    size_over_life: Vec<f32>,
+    color_over_life: Vec<LinearRgba>,
+}
+
+impl Particle {
+    /// Size at the given fraction of the particle's life.
+    fn size(&self, t: f32) -> f32 {
+        over_life(&self.size_over_life, t, 1.0, |a, b, t| a.lerp(b, t))
+    }
+
+    /// Color at the given fraction of the particle's life.
+    fn color(&self, t: f32) -> LinearRgba {
+        over_life(&self.color_over_life, t, LinearRgba::WHITE, |a, b, t| {
+            a.mix(&b, t)
+        })
+    }
+}
+
+/// Interpolates between values evenly spaced over the life of a particle.
+fn over_life<T: Copy>(values: &[T], t: f32, default: T, mix: impl Fn(T, T, f32) -> T) -> T {
+    match values {
+        [] => default,
+        [value] => *value,
+        _ => {
+            let position = t.clamp(0.0, 1.0) * (values.len() - 1) as f32;
+            let index = (position as usize).min(values.len() - 2);
+            mix(values[index], values[index + 1], position - index as f32)
+        }
+    }
+}
+
+/// A random value in the range, its start if it's empty.
+fn sample(range: &Range<f32>, rng: &mut impl Rng) -> f32 {
+    if range.is_empty() {
+        range.start
+    } else {
+        rng.random_range(range.clone())
+    }
+}
+
+fn emit_particles(
+    mut commands: Commands,
+    mut emitters: Query<(
+        Entity,
+        &ParticleEmitter,
+        &mut PendingParticles,
+        Has<DespawnAfterBurst>,
+    )>,
+    mut pool: ResMut<ParticlePool>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    // Emitters spawned this frame don't have their `GlobalTransform` yet, so it's computed.
+    mut transforms: ParamSet<(
+        TransformHelper,
+        Query<
+            (
+                &mut Mesh2d,
+                &mut MeshMaterial2d<ColorMaterial>,
+                &mut Transform,
+                &mut Visibility,
+            ),
+            With<PooledParticle>,
+        >,
+    )>,
+    time: Res<Time>,
+) {
+    let mut rng = rand::rng();
+    for (entity, emitter, mut pending, despawn_after_burst) in &mut emitters {
+        let count = match emitter.mode {
+            EmitMode::Burst(count) => {
+                if despawn_after_burst {
+                    commands.entity(entity).despawn();
+                } else {
+                    commands.entity(entity).remove::<ParticleEmitter>();
+                }
+                count
+            }
+            EmitMode::Continuous(rate) => {
+                pending.0 += rate * time.delta_secs();
+                let count = pending.0.floor();
+                pending.0 -= count;
+                count as usize
+            }
+        };
+        if count == 0 {
+            continue;
+        }
+        let Ok(origin) = transforms.p0().compute_global_transform(entity) else {
+            continue;
+        };
+        let origin = origin.translation();
+
+        for _ in 0..count {
+            let offset = Vec2::from_angle(rng.random_range(0.0..TAU))
+                * emitter.spawn_radius
+                * rng.random::<f32>().sqrt();
+            let direction = offset
+                .try_normalize()
+                .unwrap_or_else(|| Vec2::from_angle(rng.random_range(0.0..TAU)));
+            let particle = Particle {
+                age: Timer::from_seconds(sample(&emitter.lifetime, &mut rng), TimerMode::Once),
+                velocity: direction * sample(&emitter.speed, &mut rng),
+                angular_velocity: sample(&emitter.angular_velocity, &mut rng),
+                gravity: emitter.gravity,
+                drag: emitter.drag,
+                size_over_life: emitter.size_over_life.clone(),
+                color_over_life: emitter.color_over_life.clone(),
+            };
+            let mesh = pool.mesh(&emitter.shape, &mut meshes);
+            let material = pool.material(particle.color(0.0), &mut materials);
+            let transform = Transform::from_translation(origin + offset.extend(0.0))
+                .with_rotation(Quat::from_rotation_z(sample(&emitter.rotation, &mut rng)))
+                .with_scale(Vec2::splat(particle.size(0.0)).extend(1.0));
+
+            if pool.particles.len() < POOL_SIZE {
+                let pooled = commands
+                    .spawn((
+                        Name::new("Particle"),
+                        Mesh2d(mesh),
+                        MeshMaterial2d(material),
+                        transform,
+                        Pickable::IGNORE,
+                        PooledParticle,
+                        particle,
+                    ))
+                    .id();
+                pool.particles.push(pooled);
+                continue;
+            }
+
+            let pooled = pool.particles[pool.next];
+            pool.next = (pool.next + 1) % POOL_SIZE;
+            let mut particles = transforms.p1();
+            let Ok((mut pooled_mesh, mut pooled_material, mut pooled_transform, mut visibility)) =
+                particles.get_mut(pooled)
+            else {
+                continue;
+            };
+            pooled_mesh.0 = mesh;
+            pooled_material.0 = material;
+            *pooled_transform = transform;
+            *visibility = Visibility::Inherited;
+            commands.entity(pooled).insert(particle);
+        }
+    }
+}
+
+fn update_particles(
+    mut commands: Commands,
+    mut particles: Query<(
+        Entity,
+        &mut Transform,
+        &mut MeshMaterial2d<ColorMaterial>,
+        &mut Visibility,
+        &mut Particle,
+    )>,
+    mut pool: ResMut<ParticlePool>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut transform, mut material, mut visibility, mut particle) in &mut particles {
+        particle.age.tick(time.delta());
+        if particle.age.finished() {
+            // back to the pool
+            *visibility = Visibility::Hidden;
+            commands.entity(entity).remove::<Particle>();
+            continue;
+        }
+        let t = particle.age.fraction();
+
+        let (gravity, drag) = (particle.gravity, particle.drag);
+        particle.velocity += gravity * dt;
+        particle.velocity *= (-drag * dt).exp();
+        transform.translation += (particle.velocity * dt).extend(0.0);
+        transform.rotate_z(particle.angular_velocity * dt);
+
+        let size = particle.size(t);
+        transform.scale = Vec2::splat(size).extend(transform.scale.z);
+        let shared = pool.material(particle.color(t), &mut materials);
+        if material.0 != shared {
+            material.0 = shared;
+        }
+    }
+}
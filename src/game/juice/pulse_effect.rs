@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::PausableSystems;
+use crate::{PausableSystems, audio::beat_clock::BeatClock};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, apply_pulse.in_set(PausableSystems));
@@ -12,6 +12,9 @@ pub struct PulseEffect {
     pub min: f32,
     pub max: f32,
     pub speed: f32,
+    /// Pulses to the music every this many beats instead, growing to `max` on the beat and
+    /// shrinking to `min` until the next one.
+    pub beats: Option<f32>,
 }
 
 impl Default for PulseEffect {
@@ -20,6 +23,7 @@ impl Default for PulseEffect {
             min: 0.95,
             max: 1.05,
             speed: 0.2,
+            beats: None,
         }
     }
 }
@@ -37,9 +41,17 @@ impl Default for PulseEffectState {
 
 fn apply_pulse(
     mut query: Query<(&mut Transform, &mut PulseEffectState, &PulseEffect)>,
+    beat_clock: Option<Res<BeatClock>>,
     time: Res<Time>,
 ) {
     for (mut transform, mut state, effect) in query.iter_mut() {
+        if let Some((beats, beat_clock)) = effect.beats.zip(beat_clock.as_ref()) {
+            let phase = (beat_clock.position() / beats).rem_euclid(1.0);
+            let scale = effect.min.lerp(effect.max, (1.0 - phase).powi(2));
+            transform.scale = Vec2::splat(scale).extend(transform.scale.z);
+            continue;
+        }
+
         // only splat scale is supported
         let scale = transform.scale.x + state.direction * effect.speed * time.delta_secs();
 
@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::playfield::PLAYFIELD_SIZE;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, (update_hud_anchors, fill_viewport));
+}
+
+/// Keeps a world-space HUD element at a fixed place relative to the visible part of the
+/// playfield, even when the window is resized.
+#[derive(Component)]
+#[require(Transform)]
+pub struct HudAnchor {
+    /// Position relative to the visible playfield, from `(-1, -1)` in the bottom left corner to
+    /// `(1, 1)` in the top right corner.
+    pub anchor: Vec2,
+    /// Offset from the anchor in world units.
+    pub offset: Vec2,
+}
+
+impl HudAnchor {
+    pub fn new(anchor: Vec2) -> Self {
+        Self {
+            anchor,
+            offset: Vec2::ZERO,
+        }
+    }
+}
+
+/// Scales an entity with a unit-sized mesh so it always covers the whole viewport.
+#[derive(Component)]
+#[require(Transform)]
+pub struct FillViewport;
+
+/// Visible area of the world. Changes whenever the window is resized or the scaling mode changes.
+fn visible_area(projection: &Projection) -> Option<Rect> {
+    match projection {
+        Projection::Orthographic(orthographic) => Some(orthographic.area),
+        _ => None,
+    }
+}
+
+fn update_hud_anchors(
+    camera: Single<Ref<Projection>, With<Camera2d>>,
+    mut query: Query<(Ref<HudAnchor>, &mut Transform)>,
+) {
+    let Some(area) = visible_area(&camera) else {
+        return;
+    };
+    let half_size = area.half_size().min(PLAYFIELD_SIZE * 0.5);
+
+    for (hud_anchor, mut transform) in query.iter_mut() {
+        if !camera.is_changed() && !hud_anchor.is_changed() {
+            continue;
+        }
+
+        let position = hud_anchor.anchor * half_size + hud_anchor.offset;
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+fn fill_viewport(
+    camera: Single<Ref<Projection>, With<Camera2d>>,
+    mut query: Query<(Ref<FillViewport>, &mut Transform)>,
+) {
+    let Some(area) = visible_area(&camera) else {
+        return;
+    };
+
+    for (fill, mut transform) in query.iter_mut() {
+        if !camera.is_changed() && !fill.is_added() {
+            continue;
+        }
+
+        transform.translation = area.center().extend(transform.translation.z);
+        transform.scale = area.size().extend(transform.scale.z);
+    }
+}
@@ -1,23 +1,31 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 
 use crate::{
-    audio::sound_effect,
+    audio::sfx::PlaySfx,
     game::{
-        GameOver,
-        bar::{Bar, BarBehavior, BarLayout, OnBarEmpty},
-        game_sequencer::GameMechanic,
+        GameOverContext,
+        bar::{Bar, BarAnimation, BarBehavior, BarLabel, BarLayout, BarWarning, OnBarEmpty},
+        game_sequencer::{GameMechanic, MechanicEntity},
         juice::{circles::SpawnCircles, pulse_effect::PulseEffect},
-        mechanics::the_button::{OnButtonClicked, THE_BUTTON_Z},
+        layout::HudAnchor,
+        mechanics::the_button::{OnButtonClicked, THE_BUTTON_Z, TheButton},
         player::CLICK_PARTICLES_Z,
     },
+    localization::LocalizedText,
     screens::Screen,
 };
 
 const MAX_DURABILITY: f32 = 6.0;
 const CLICK_DAMAGE: f32 = 1.0;
 const BAR_COLOR: Color = Color::linear_rgb(1.0, 1.0, 0.0);
+const BAR_EMPTY_COLOR: Color = Color::linear_rgb(1.0, 0.0, 0.0);
+/// Durability (0..1) at which the bar starts flashing, i.e. two clicks left.
+const BAR_WARNING: f32 = 0.34;
+
+const DURABILITY_BAR_ANCHOR: Vec2 = vec2(-0.48, -0.84);
 
 const FIX_BUTTON_SIZE: f32 = 40.0;
+const FIX_BUTTON_ANCHOR: Vec2 = vec2(-0.91, -0.84);
 const TEXT_SIZE: f32 = 32.0;
 const TEXT_COLOR: Color = Color::linear_rgb(0.0, 0.0, 0.0);
 
@@ -28,13 +36,16 @@ pub(super) fn plugin(app: &mut App) {
         .add_observer(on_durability_zero);
 }
 
+#[derive(Event)]
+pub struct OnFixClicked;
+
 #[derive(Component)]
 struct DurabilityBar;
 
 #[derive(Component)]
 struct FixButton;
 
-fn spawn_durability_bar(mut commands: Commands, window: Single<&Window, With<PrimaryWindow>>) {
+fn spawn_durability_bar(mut commands: Commands) {
     commands.spawn((
         Name::new("Durability bar"),
         Bar {
@@ -44,20 +55,30 @@ fn spawn_durability_bar(mut commands: Commands, window: Single<&Window, With<Pri
         },
         BarLayout {
             color: BAR_COLOR,
+            empty_color: Some(BAR_EMPTY_COLOR),
             size: vec2(448.0, 32.0),
+            label: Some(BarLabel {
+                font_size: TEXT_SIZE * 0.75,
+                color: TEXT_COLOR,
+                ..default()
+            }),
+            segments: Some(MAX_DURABILITY as u32),
+            warning: Some(BarWarning {
+                threshold: BAR_WARNING,
+                ..default()
+            }),
             ..default()
         },
         BarBehavior {
             trigger_on_empty: true,
             ..default()
         },
-        Transform::from_xyz(
-            window.width() * -0.24,
-            window.height() * -0.42,
-            THE_BUTTON_Z,
-        ),
+        BarAnimation::default(),
+        Transform::from_xyz(0.0, 0.0, THE_BUTTON_Z),
+        HudAnchor::new(DURABILITY_BAR_ANCHOR),
         StateScoped(Screen::Gameplay),
         DurabilityBar,
+        MechanicEntity(GameMechanic::Durability),
         Pickable {
             should_block_lower: false,
             ..default()
@@ -67,8 +88,6 @@ fn spawn_durability_bar(mut commands: Commands, window: Single<&Window, With<Pri
 
 fn spawn_fix_button(
     mut commands: Commands,
-    window: Single<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
@@ -77,25 +96,24 @@ fn spawn_fix_button(
             Name::new("Fix button"),
             Mesh2d(meshes.add(Circle::new(FIX_BUTTON_SIZE))),
             MeshMaterial2d(materials.add(BAR_COLOR)),
-            Transform::from_xyz(
-                window.width() * -0.455,
-                window.height() * -0.42,
-                THE_BUTTON_Z,
-            ),
+            Transform::from_xyz(0.0, 0.0, THE_BUTTON_Z),
+            HudAnchor::new(FIX_BUTTON_ANCHOR),
             StateScoped(Screen::Gameplay),
             PulseEffect {
                 min: 0.98,
                 max: 1.02,
                 speed: 0.1,
+                ..default()
             },
             FixButton,
+            MechanicEntity(GameMechanic::Fix),
             Pickable::default(),
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text2d::new("FIX"),
+                LocalizedText::new("@button.fix"),
+                Text2d::default(),
                 TextFont {
-                    font: asset_server.load("fonts/Super Vanilla.ttf"),
                     font_size: TEXT_SIZE,
                     ..default()
                 },
@@ -118,11 +136,13 @@ fn on_durability_zero(
     trigger: Trigger<OnBarEmpty>,
     mut commands: Commands,
     durability_bar_entity: Single<Entity, With<DurabilityBar>>,
+    button: Single<Entity, With<TheButton>>,
+    game_over_context: GameOverContext,
 ) {
     if trigger.event().sender != *durability_bar_entity {
         return;
     }
-    commands.trigger(GameOver(GameMechanic::Durability));
+    commands.trigger(game_over_context.game_over(GameMechanic::Durability, Some(*button)));
 }
 
 fn on_fix_button_click(
@@ -130,14 +150,13 @@ fn on_fix_button_click(
     mut commands: Commands,
     mut durability_bar: Single<&mut Bar, With<DurabilityBar>>,
     button_transform: Single<&Transform, With<FixButton>>,
-    asset_server: Res<AssetServer>,
 ) {
     // fill durability
     durability_bar.current = durability_bar.max;
+    commands.trigger(OnFixClicked);
 
     // play sound effect
-    let handle = asset_server.load("audio/sound_effects/button_click.ogg");
-    commands.spawn((Name::new("Button click sound"), sound_effect(handle, 0.4)));
+    commands.trigger(PlaySfx("button_click"));
 
     // trigger circles effect
     commands.trigger(SpawnCircles {
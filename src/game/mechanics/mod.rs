@@ -1,11 +1,11 @@
 use bevy::prelude::*;
 
-mod durability;
-mod pentagon;
+pub mod durability;
+pub mod pentagon;
 mod square;
-mod the_button;
-mod timer;
-mod triangles;
+pub mod the_button;
+pub mod timer;
+pub mod triangles;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
@@ -5,10 +5,16 @@ use rand::Rng;
 
 use crate::{
     PausableSystems,
+    audio::{
+        sfx::PlaySfx,
+        spatial::{ArrivalWarning, Proximity},
+    },
     game::{
-        GameOver,
-        game_sequencer::GameMechanic,
+        GameOverContext,
+        game_sequencer::{GameMechanic, MechanicEntity},
+        juice::particles::{EmitMode, ParticleEmitter, ParticleShape},
         player::{PLAYER_SIZE, Player},
+        practice::ThreatTuning,
     },
     screens::Screen,
 };
@@ -17,8 +23,12 @@ const PENTAGON_Z: f32 = 95.0;
 const PENTAGON_COLOR: Color = Color::linear_rgb(1.0, 0.5, 0.0);
 const PENTAGON_SIZE: f32 = 32.0;
 const PENTAGON_SPEED: f32 = 192.0;
+/// Particles per second of the trail the pentagon leaves behind.
+const TRAIL_RATE: f32 = 12.0;
 
 const SPAWN_DISTANCE: f32 = 1024.0;
+/// Distance from the player at which the pentagon warns that it's about to catch them.
+const WARNING_DISTANCE: f32 = 300.0;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(GameMechanic::Pentagon), spawn_pentagon)
@@ -26,43 +36,79 @@ pub(super) fn plugin(app: &mut App) {
 }
 
 #[derive(Component)]
-struct Pentagon;
+pub struct Pentagon;
 
 fn spawn_pentagon(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    player: Query<Entity, With<Player>>,
 ) {
     let mut rng = rand::rng();
     let position = Vec2::from_angle(rng.random_range(0.0..2.0 * PI)) * SPAWN_DISTANCE;
 
-    commands.spawn((
+    let mesh = meshes.add(RegularPolygon::new(PENTAGON_SIZE, 5));
+    let mut pentagon = commands.spawn((
         Name::new("Pentagon"),
-        Mesh2d(meshes.add(RegularPolygon::new(PENTAGON_SIZE, 5))),
+        Mesh2d(mesh.clone()),
         MeshMaterial2d(materials.add(PENTAGON_COLOR)),
         Transform::from_translation(position.extend(PENTAGON_Z)),
         StateScoped(Screen::Gameplay),
         Pentagon,
+        MechanicEntity(GameMechanic::Pentagon),
+        children![(
+            Name::new("Pentagon trail"),
+            // behind the pentagon
+            Transform::from_xyz(0.0, 0.0, -1.0),
+            trail_emitter(mesh),
+        )],
     ));
+    if let Ok(player) = player.single() {
+        pentagon.insert((
+            Proximity {
+                target: player,
+                range: SPAWN_DISTANCE,
+            },
+            ArrivalWarning::new("threat_near", WARNING_DISTANCE),
+        ));
+    }
+    let pentagon = pentagon.id();
+    commands.trigger_targets(PlaySfx("threat_spawn"), pentagon);
+}
+
+/// Fading, shrinking copies of the pentagon left behind as it moves.
+fn trail_emitter(mesh: Handle<Mesh>) -> ParticleEmitter {
+    let color = PENTAGON_COLOR.to_linear();
+    ParticleEmitter {
+        mode: EmitMode::Continuous(TRAIL_RATE),
+        shape: ParticleShape::Mesh(mesh),
+        lifetime: 0.5..0.7,
+        size_over_life: vec![0.6, 0.2],
+        color_over_life: vec![color.with_alpha(0.5), color.with_alpha(0.0)],
+        ..default()
+    }
 }
 
 fn move_to_player(
     mut commands: Commands,
-    mut pentagon_transform: Single<&mut Transform, With<Pentagon>>,
+    pentagon: Single<(Entity, &mut Transform), With<Pentagon>>,
     player_transform: Single<&mut Transform, (With<Player>, Without<Pentagon>)>,
+    game_over_context: GameOverContext,
+    tuning: Res<ThreatTuning>,
     time: Res<Time>,
 ) {
+    let (pentagon, mut pentagon_transform) = pentagon.into_inner();
     let pentagon_position = pentagon_transform.translation.xy();
     let player_position = player_transform.translation.xy();
 
     let direction = (player_position - pentagon_position).normalize_or_zero();
-    let delta = direction * PENTAGON_SPEED * time.delta_secs();
+    let delta = direction * PENTAGON_SPEED * tuning.speed * time.delta_secs();
 
     pentagon_transform.translation += delta.extend(0.0);
     pentagon_transform.rotation = Quat::from_rotation_z(direction.to_angle());
 
     if player_position.distance_squared(pentagon_position) <= (PENTAGON_SIZE + PLAYER_SIZE).powi(2)
     {
-        commands.trigger(GameOver(GameMechanic::Pentagon));
+        commands.trigger(game_over_context.game_over(GameMechanic::Pentagon, Some(pentagon)));
     }
 }
@@ -1,11 +1,22 @@
 use std::f32::consts::PI;
 
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 use rand::Rng;
 
+#[cfg(feature = "dev")]
+use crate::game::game_sequencer::RetireMechanic;
 use crate::{
     PausableSystems,
-    game::{game_sequencer::GameMechanic, mechanics::the_button::TheButton},
+    audio::{
+        sfx::PlaySfx,
+        spatial::{ArrivalWarning, Proximity},
+    },
+    game::{
+        game_sequencer::{GameMechanic, MechanicEntity},
+        mechanics::the_button::TheButton,
+        practice::ThreatTuning,
+    },
+    playfield::PLAYFIELD_SIZE,
     screens::Screen,
 };
 
@@ -14,11 +25,16 @@ const SQUARE_SIZE: f32 = 256.0;
 const SQUARE_Z: f32 = 90.0;
 const SQUARE_COLOR: Color = Color::linear_rgb(0.5, 0.0, 1.0);
 const SQUARE_SPEED: f32 = 1024.0;
+/// Distance from THE BUTTON at which the square warns that it's about to arrive.
+const SQUARE_WARNING_DISTANCE: f32 = 400.0;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(GameMechanic::Square), spawn_square_spawner)
         .add_systems(Update, (spawn_square, move_square).in_set(PausableSystems))
         .add_systems(OnExit(Screen::Gameplay), despawn_square_spawner);
+
+    #[cfg(feature = "dev")]
+    app.add_observer(retire_square_spawner);
 }
 
 #[derive(Resource)]
@@ -37,9 +53,13 @@ fn spawn_square_spawner(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    tuning: Res<ThreatTuning>,
 ) {
     commands.insert_resource(SquareSpawner {
-        spawn_timer: Timer::from_seconds(SQUARE_SPAWN_INTERVAL, TimerMode::Once),
+        spawn_timer: Timer::from_seconds(
+            SQUARE_SPAWN_INTERVAL * tuning.spawn_interval,
+            TimerMode::Once,
+        ),
         mesh: meshes.add(Rectangle::new(SQUARE_SIZE, SQUARE_SIZE)),
         material: materials.add(SQUARE_COLOR),
     });
@@ -49,10 +69,17 @@ fn despawn_square_spawner(mut commands: Commands) {
     commands.remove_resource::<SquareSpawner>();
 }
 
+#[cfg(feature = "dev")]
+fn retire_square_spawner(trigger: Trigger<RetireMechanic>, mut commands: Commands) {
+    if trigger.event().0 == GameMechanic::Square {
+        commands.remove_resource::<SquareSpawner>();
+    }
+}
+
 fn spawn_square(
     mut commands: Commands,
-    window: Single<&Window, With<PrimaryWindow>>,
     spawner: Option<ResMut<SquareSpawner>>,
+    button: Query<Entity, With<TheButton>>,
     time: Res<Time>,
 ) {
     let Some(mut spawner) = spawner else {
@@ -66,30 +93,41 @@ fn spawn_square(
 
     let mut rng = rand::rng();
     let angle = rng.random_range((0.0)..(2.0 * PI));
-    let position = Vec2::from_angle(angle) * window.width() * 0.6;
-
-    commands
-        .spawn((
-            Name::new("Square"),
-            Mesh2d(spawner.mesh.clone()),
-            MeshMaterial2d(spawner.material.clone()),
-            Transform::from_translation(position.extend(SQUARE_Z)),
-            Pickable {
-                should_block_lower: true,
-                ..default()
+    let position = Vec2::from_angle(angle) * PLAYFIELD_SIZE.x * 0.6;
+
+    let mut square = commands.spawn((
+        Name::new("Square"),
+        Mesh2d(spawner.mesh.clone()),
+        MeshMaterial2d(spawner.material.clone()),
+        Transform::from_translation(position.extend(SQUARE_Z)),
+        Pickable {
+            should_block_lower: true,
+            ..default()
+        },
+        Square::default(),
+        MechanicEntity(GameMechanic::Square),
+        StateScoped(Screen::Gameplay),
+    ));
+    square.observe(on_square_drag);
+    if let Ok(button) = button.single() {
+        square.insert((
+            Proximity {
+                target: button,
+                range: position.length(),
             },
-            Square::default(),
-            StateScoped(Screen::Gameplay),
-        ))
-        .observe(on_square_drag);
+            ArrivalWarning::new("threat_near", SQUARE_WARNING_DISTANCE),
+        ));
+    }
+    let square = square.id();
+    commands.trigger_targets(PlaySfx("threat_spawn"), square);
 }
 
 fn move_square(
     mut commands: Commands,
     square: Single<(Entity, &mut Transform, &Square)>,
     button_transform: Single<&Transform, (With<TheButton>, Without<Square>)>,
-    window: Single<&Window, With<PrimaryWindow>>,
     spawner: Option<ResMut<SquareSpawner>>,
+    tuning: Res<ThreatTuning>,
     time: Res<Time>,
 ) {
     let (entity, mut square_transform, square) = square.into_inner();
@@ -104,13 +142,13 @@ fn move_square(
     let direction = square
         .drag_direction
         .unwrap_or_else(|| (button_pos - square_pos).normalize_or_zero());
-    let delta = direction * SQUARE_SPEED * time.delta_secs();
+    let delta = direction * SQUARE_SPEED * tuning.speed * time.delta_secs();
 
     square_transform.translation += delta.extend(0.0);
     square_transform.rotation = Quat::from_rotation_z(direction.to_angle());
 
     if square.drag_direction.is_some()
-        && button_pos.distance_squared(square_pos) >= window.width() * window.width()
+        && button_pos.distance_squared(square_pos) >= PLAYFIELD_SIZE.x * PLAYFIELD_SIZE.x
     {
         commands.entity(entity).despawn();
         spawner.unwrap().spawn_timer.reset();
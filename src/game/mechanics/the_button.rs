@@ -2,14 +2,19 @@ use bevy::prelude::*;
 
 use crate::{
     PausableSystems,
-    audio::sound_effect,
+    audio::sfx::PlaySfx,
     game::{
-        GameOver,
-        bar::{Bar, BarBehavior, BarLayout, OnBarEmpty},
-        game_sequencer::GameMechanic,
+        GameOverContext,
+        bar::{
+            Bar, BarBehavior, BarLabel, BarLabelFormat, BarLayout, BarWarning, CrossingDirection,
+            OnBarEmpty, OnBarThreshold,
+        },
+        game_sequencer::{GameMechanic, MechanicEntity},
+        guide::{GuideMessage, GuidePriority},
         juice::{circles::SpawnCircles, pulse_effect::PulseEffect},
         player::{CLICK_PARTICLES_Z, Player},
     },
+    localization::LocalizedText,
     screens::Screen,
 };
 
@@ -21,12 +26,15 @@ const TEXT_SIZE: f32 = 32.0;
 const TEXT_COLOR: Color = Color::linear_rgb(0.0, 0.0, 0.0);
 
 const TIME_BAR_DURATION: f32 = 8.0;
+const TIME_BAR_WARNING: f32 = 0.3;
+const BAR_EMPTY_COLOR: Color = Color::linear_rgb(1.0, 0.0, 0.0);
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(GameMechanic::Button), spawn_button)
         .add_systems(OnEnter(GameMechanic::ButtonTime), spawn_button_time_bar)
         .add_systems(Update, update_button_time.in_set(PausableSystems))
-        .add_observer(on_button_time_up);
+        .add_observer(on_button_time_up)
+        .add_observer(warn_on_button_time_low);
 }
 
 #[derive(Event)]
@@ -36,11 +44,10 @@ pub struct OnButtonClicked;
 pub struct TheButton;
 
 #[derive(Component)]
-struct ButtonTimeBar;
+pub struct ButtonTimeBar;
 
 fn spawn_button(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
@@ -51,14 +58,18 @@ fn spawn_button(
             MeshMaterial2d(materials.add(THE_BUTTON_COLOR)),
             Transform::from_xyz(0.0, 0.0, THE_BUTTON_Z),
             TheButton,
+            MechanicEntity(GameMechanic::Button),
             StateScoped(Screen::Gameplay),
-            PulseEffect::default(),
+            PulseEffect {
+                beats: Some(1.0),
+                ..default()
+            },
         ))
         .with_children(|parent| {
             parent.spawn((
-                Text2d::new("Click\nme!"),
+                LocalizedText::new("@button.click_me"),
+                Text2d::default(),
                 TextFont {
-                    font: asset_server.load("fonts/Super Vanilla.ttf"),
                     font_size: TEXT_SIZE,
                     ..default()
                 },
@@ -80,14 +91,27 @@ fn spawn_button_time_bar(mut commands: Commands) {
         },
         BarLayout {
             color: THE_BUTTON_COLOR,
+            empty_color: Some(BAR_EMPTY_COLOR),
+            warning: Some(BarWarning {
+                threshold: TIME_BAR_WARNING,
+                ..default()
+            }),
+            label: Some(BarLabel {
+                format: BarLabelFormat::Custom(|bar| format!("{:.1}", bar.current)),
+                font_size: 12.0,
+                color: TEXT_COLOR,
+                ..default()
+            }),
             ..default()
         },
         BarBehavior {
             trigger_on_empty: true,
+            thresholds: vec![TIME_BAR_WARNING],
             ..default()
         },
         Transform::from_xyz(0.0, -THE_BUTTON_SIZE * 1.5, THE_BUTTON_Z),
         ButtonTimeBar,
+        MechanicEntity(GameMechanic::ButtonTime),
         StateScoped(Screen::Gameplay),
         Pickable {
             should_block_lower: false,
@@ -104,11 +128,44 @@ fn on_button_time_up(
     trigger: Trigger<OnBarEmpty>,
     mut commands: Commands,
     time_bar_entity: Single<Entity, With<ButtonTimeBar>>,
+    button: Single<Entity, With<TheButton>>,
+    game_over_context: GameOverContext,
 ) {
     if trigger.event().sender != *time_bar_entity {
         return;
     }
-    commands.trigger(GameOver(GameMechanic::ButtonTime));
+    commands.trigger(game_over_context.game_over(GameMechanic::ButtonTime, Some(*button)));
+}
+
+fn warn_on_button_time_low(
+    trigger: Trigger<OnBarThreshold>,
+    mut commands: Commands,
+    time_bar_entity: Single<Entity, With<ButtonTimeBar>>,
+    button_transform: Single<&Transform, With<TheButton>>,
+) {
+    let event = trigger.event();
+    if event.sender != *time_bar_entity
+        || event.threshold != TIME_BAR_WARNING
+        || event.crossing_direction != CrossingDirection::Falling
+    {
+        return;
+    }
+
+    commands.trigger(SpawnCircles {
+        location: button_transform.translation.xy().extend(CLICK_PARTICLES_Z),
+        start_size: THE_BUTTON_SIZE * 1.1,
+        end_size: THE_BUTTON_SIZE * 1.8,
+        start_color: BAR_EMPTY_COLOR.to_linear(),
+        thickness: 6.0,
+        spacing: 12.0,
+        ttl: 0.8,
+        ..default()
+    });
+    commands.trigger(
+        GuideMessage::new("@guide.click_the_button")
+            .with_duration(1.5)
+            .with_priority(GuidePriority::Urgent),
+    );
 }
 
 fn handle_button_click(
@@ -116,14 +173,12 @@ fn handle_button_click(
     mut commands: Commands,
     mut player: Single<&mut Player>,
     button_transform: Single<&Transform, With<TheButton>>,
-    asset_server: Res<AssetServer>,
 ) {
     player.clicked_on_target = true;
     commands.trigger(OnButtonClicked);
 
     // play click sound
-    let handle = asset_server.load("audio/sound_effects/button_click.ogg");
-    commands.spawn((Name::new("Button click sound"), sound_effect(handle, 0.4)));
+    commands.trigger(PlaySfx("button_click"));
 
     // play circles effect
     commands.trigger(SpawnCircles {
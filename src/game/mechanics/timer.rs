@@ -1,6 +1,12 @@
 use bevy::prelude::*;
 
-use crate::{PausableSystems, game::game_sequencer::GameMechanic, screens::Screen};
+#[cfg(feature = "dev")]
+use crate::game::game_sequencer::RetireMechanic;
+use crate::{
+    PausableSystems,
+    game::game_sequencer::{GameMechanic, MechanicEntity},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(GameMechanic::Timer), spawn_timer)
@@ -13,15 +19,18 @@ pub(super) fn plugin(app: &mut App) {
             )
                 .in_set(PausableSystems),
         );
+
+    #[cfg(feature = "dev")]
+    app.add_observer(retire_elapsed_time);
 }
 
 #[derive(Resource, Default)]
-pub struct ElapsedTime(f32);
+pub struct ElapsedTime(pub f32);
 
 #[derive(Component)]
 struct TimerText;
 
-fn spawn_timer(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn spawn_timer(mut commands: Commands) {
     commands
         .spawn((
             Node {
@@ -32,6 +41,7 @@ fn spawn_timer(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ..default()
             },
             StateScoped(Screen::Gameplay),
+            MechanicEntity(GameMechanic::Timer),
             Pickable {
                 should_block_lower: false,
                 ..default()
@@ -41,7 +51,6 @@ fn spawn_timer(mut commands: Commands, asset_server: Res<AssetServer>) {
             parent.spawn((
                 Text::new("00:00"),
                 TextFont {
-                    font: asset_server.load("fonts/Super Vanilla.ttf"),
                     font_size: 64.0,
                     ..default()
                 },
@@ -67,6 +76,13 @@ fn despawn_timer(mut commands: Commands) {
     commands.remove_resource::<ElapsedTime>();
 }
 
+#[cfg(feature = "dev")]
+fn retire_elapsed_time(trigger: Trigger<RetireMechanic>, mut commands: Commands) {
+    if trigger.event().0 == GameMechanic::Timer {
+        commands.remove_resource::<ElapsedTime>();
+    }
+}
+
 fn increment_time(time: Res<Time>, mut elapsed_time: ResMut<ElapsedTime>) {
     elapsed_time.0 += time.delta_secs();
 }
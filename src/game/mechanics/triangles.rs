@@ -1,15 +1,26 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
 use rand::Rng;
 
+#[cfg(feature = "dev")]
+use crate::game::game_sequencer::RetireMechanic;
 use crate::{
     PausableSystems,
-    audio::sound_effect,
+    audio::{
+        beat_clock::{BeatClock, BeatEvent},
+        sfx::PlaySfx,
+        spatial::{ArrivalWarning, Proximity},
+    },
     game::{
-        GameOver,
-        game_sequencer::GameMechanic,
+        GameOverContext,
+        game_sequencer::{ActionSequence, GameMechanic, MechanicEntity, SequencerState},
+        juice::particles::{EmitMode, ParticleEmitter, ParticleShape, particle_burst},
         mechanics::the_button::{THE_BUTTON_SIZE, TheButton},
         player::Player,
+        practice::ThreatTuning,
     },
+    playfield::PLAYFIELD_SIZE,
     screens::Screen,
 };
 
@@ -18,6 +29,8 @@ const TRIANGLE_SIZE: f32 = 48.0;
 const TRIANGLE_COLOR: Color = Color::linear_rgb(0.0, 0.0, 1.0);
 const TRIANGLE_Z: f32 = 80.0;
 const TRIANGLE_SPEED: f32 = 96.0;
+/// Distance from THE BUTTON at which a triangle warns that it's about to arrive.
+const TRIANGLE_WARNING_DISTANCE: f32 = 300.0;
 
 const FRAGMENT_SIZE: f32 = 12.0;
 const FRAGMENTS_PER_HEXAGON: usize = 8;
@@ -32,10 +45,13 @@ pub(super) fn plugin(app: &mut App) {
         )
         .add_observer(create_triangle_destroyed_effect)
         .add_systems(OnExit(Screen::Gameplay), despawn_triangle_spawner);
+
+    #[cfg(feature = "dev")]
+    app.add_observer(retire_triangle_spawner);
 }
 
 #[derive(Event)]
-struct OnTriangleDestroyed {
+pub struct OnTriangleDestroyed {
     location: Vec2,
 }
 
@@ -45,23 +61,29 @@ struct Triangle;
 #[derive(Resource)]
 struct TriangleSpawner {
     spawn_timer: Timer,
+    /// Whether triangles spawn on the beat, when the sequence spawned the mechanic on the beat.
+    on_beat: bool,
+    /// Whether a triangle waits for the next beat to spawn.
+    waiting_for_beat: bool,
     triangle_mesh: Handle<Mesh>,
     triangle_material: Handle<ColorMaterial>,
 }
 
-#[derive(Resource)]
-struct FragmentHandles {
-    mesh: Handle<Mesh>,
-    material: Handle<ColorMaterial>,
-}
-
 fn spawn_triangle_spawner(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    sequencer: Res<SequencerState>,
+    action_sequences: Res<Assets<ActionSequence>>,
+    tuning: Res<ThreatTuning>,
 ) {
     commands.insert_resource(TriangleSpawner {
-        spawn_timer: Timer::from_seconds(TRIANGLE_SPAWN_INTERVAL, TimerMode::Repeating),
+        spawn_timer: Timer::from_seconds(
+            TRIANGLE_SPAWN_INTERVAL * tuning.spawn_interval,
+            TimerMode::Repeating,
+        ),
+        on_beat: sequencer.spawns_on_beat(&action_sequences, GameMechanic::Triangles),
+        waiting_for_beat: false,
         triangle_mesh: meshes.add(Triangle2d::new(
             vec2(0.0, 0.0),
             vec2(-TRIANGLE_SIZE, TRIANGLE_SIZE * 0.6),
@@ -69,66 +91,84 @@ fn spawn_triangle_spawner(
         )),
         triangle_material: materials.add(TRIANGLE_COLOR),
     });
-
-    commands.insert_resource(FragmentHandles {
-        mesh: meshes.add(Triangle2d::new(
-            vec2(-FRAGMENT_SIZE / 2.0, -FRAGMENT_SIZE / 2.0),
-            vec2(FRAGMENT_SIZE / 2.0, -FRAGMENT_SIZE / 2.0),
-            vec2(-FRAGMENT_SIZE / 2.0, FRAGMENT_SIZE / 2.0),
-        )),
-        material: materials.add(FRAGMENT_COLOR),
-    });
 }
 
 fn despawn_triangle_spawner(mut commands: Commands) {
     commands.remove_resource::<TriangleSpawner>();
-    commands.remove_resource::<FragmentHandles>();
+}
+
+#[cfg(feature = "dev")]
+fn retire_triangle_spawner(trigger: Trigger<RetireMechanic>, mut commands: Commands) {
+    if trigger.event().0 == GameMechanic::Triangles {
+        commands.remove_resource::<TriangleSpawner>();
+    }
 }
 
 fn spawn_triangles(
     mut commands: Commands,
-    window: Single<&Window, With<PrimaryWindow>>,
     spawner: Option<ResMut<TriangleSpawner>>,
+    button: Query<Entity, With<TheButton>>,
+    beat_clock: Option<Res<BeatClock>>,
+    mut beats: EventReader<BeatEvent>,
     time: Res<Time>,
 ) {
+    let on_beat = beats.read().count() > 0;
     let Some(mut spawner) = spawner else {
         return;
     };
 
     spawner.spawn_timer.tick(time.delta());
-    if !spawner.spawn_timer.finished() {
+    if spawner.spawn_timer.finished() {
+        spawner.waiting_for_beat = true;
+    }
+    // triangles land on the music, if there is any
+    if !spawner.waiting_for_beat || (spawner.on_beat && beat_clock.is_some() && !on_beat) {
         return;
     }
+    spawner.waiting_for_beat = false;
 
     let mut rng = rand::rng();
     let spawn_position = vec2(
-        window.width() * 0.7,
-        rng.random_range((-window.height() * 0.4)..(window.height() * 0.4)),
+        PLAYFIELD_SIZE.x * 0.7,
+        rng.random_range((-PLAYFIELD_SIZE.y * 0.4)..(PLAYFIELD_SIZE.y * 0.4)),
     );
 
-    commands
-        .spawn((
-            Name::new("Triangle"),
-            Mesh2d(spawner.triangle_mesh.clone()),
-            MeshMaterial2d(spawner.triangle_material.clone()),
-            Transform::from_translation(spawn_position.extend(TRIANGLE_Z)),
-            Pickable::default(),
-            Triangle,
-            StateScoped(Screen::Gameplay),
-        ))
-        .observe(destroy_clicked_hexagon);
+    let mut triangle = commands.spawn((
+        Name::new("Triangle"),
+        Mesh2d(spawner.triangle_mesh.clone()),
+        MeshMaterial2d(spawner.triangle_material.clone()),
+        Transform::from_translation(spawn_position.extend(TRIANGLE_Z)),
+        Pickable::default(),
+        Triangle,
+        MechanicEntity(GameMechanic::Triangles),
+        StateScoped(Screen::Gameplay),
+    ));
+    triangle.observe(destroy_clicked_hexagon);
+    if let Ok(button) = button.single() {
+        triangle.insert((
+            Proximity {
+                target: button,
+                range: spawn_position.length(),
+            },
+            ArrivalWarning::new("threat_near", TRIANGLE_WARNING_DISTANCE),
+        ));
+    }
+    let triangle = triangle.id();
+    commands.trigger_targets(PlaySfx("threat_spawn"), triangle);
 }
 
 fn move_triangles(
     mut commands: Commands,
-    mut query: Query<&mut Transform, (With<Triangle>, Without<TheButton>)>,
+    mut query: Query<(Entity, &mut Transform), (With<Triangle>, Without<TheButton>)>,
     button_transform: Single<&Transform, With<TheButton>>,
+    game_over_context: GameOverContext,
+    tuning: Res<ThreatTuning>,
     time: Res<Time>,
 ) {
-    for mut transform in query.iter_mut() {
+    for (entity, mut transform) in query.iter_mut() {
         let direction =
             (button_transform.translation.xy() - transform.translation.xy()).normalize_or_zero();
-        let delta = direction * TRIANGLE_SPEED * time.delta_secs();
+        let delta = direction * TRIANGLE_SPEED * tuning.speed * time.delta_secs();
 
         transform.translation += delta.extend(0.0);
         transform.rotation = Quat::from_rotation_z(direction.to_angle());
@@ -138,7 +178,7 @@ fn move_triangles(
             .xy()
             .distance(button_transform.translation.xy());
         if distance <= THE_BUTTON_SIZE {
-            commands.trigger(GameOver(GameMechanic::Triangles));
+            commands.trigger(game_over_context.game_over(GameMechanic::Triangles, Some(entity)));
             return;
         }
     }
@@ -159,38 +199,31 @@ fn destroy_clicked_hexagon(
     commands.entity(trigger.target()).despawn();
 }
 
-fn create_triangle_destroyed_effect(
-    trigger: Trigger<OnTriangleDestroyed>,
-    mut commands: Commands,
-    fragment_handles: Res<FragmentHandles>,
-    asset_server: Res<AssetServer>,
-) {
-    let mut rng = rand::rng();
+/// Fragments flying apart from a destroyed triangle, spinning and fading out as they slow down.
+fn fragment_emitter() -> ParticleEmitter {
+    let color = FRAGMENT_COLOR.to_linear();
+    ParticleEmitter {
+        mode: EmitMode::Burst(FRAGMENTS_PER_HEXAGON),
+        shape: ParticleShape::Triangle,
+        spawn_radius: TRIANGLE_SIZE * 0.3,
+        speed: 40.0..120.0,
+        drag: 3.0,
+        rotation: 0.0..TAU,
+        angular_velocity: -6.0..6.0,
+        lifetime: 1.0..1.6,
+        size_over_life: vec![FRAGMENT_SIZE],
+        color_over_life: vec![color, color, color.with_alpha(0.0)],
+        ..default()
+    }
+}
 
+fn create_triangle_destroyed_effect(trigger: Trigger<OnTriangleDestroyed>, mut commands: Commands) {
     // spawn fragments
-    for _ in 0..FRAGMENTS_PER_HEXAGON {
-        let relative_position = vec2(
-            rng.random_range((-TRIANGLE_SIZE * 0.3)..(TRIANGLE_SIZE * 0.3)),
-            rng.random_range((-TRIANGLE_SIZE * 0.3)..(TRIANGLE_SIZE * 0.3)),
-        );
-        let position = trigger.event().location + relative_position;
-        let rotation = rng.random_range((0.0)..(2.0 * std::f32::consts::PI));
-
-        commands.spawn((
-            Name::new("Fragment"),
-            Mesh2d(fragment_handles.mesh.clone()),
-            MeshMaterial2d(fragment_handles.material.clone()),
-            Transform::from_translation(position.extend(FRAGMENT_Z))
-                .with_rotation(Quat::from_rotation_z(rotation)),
-            Pickable {
-                should_block_lower: false,
-                ..default()
-            },
-            StateScoped(Screen::Gameplay),
-        ));
-    }
+    commands.spawn(particle_burst(
+        trigger.event().location.extend(FRAGMENT_Z),
+        fragment_emitter(),
+    ));
 
     // play sound effect
-    let handle = asset_server.load("audio/sound_effects/break.ogg");
-    commands.spawn((Name::new("Hexagon break sound"), sound_effect(handle, 0.2)));
+    commands.trigger(PlaySfx("break"));
 }
@@ -1,73 +1,130 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 
 use crate::{
-    asset_tracking::LoadResource,
-    audio::{music, sound_effect},
-    game::game_sequencer::GameMechanic,
+    game::{
+        bar::Bar, death_freeze::DeathFreeze, game_sequencer::GameMechanic,
+        mechanics::timer::ElapsedTime, soundtrack::PlayStinger,
+    },
     screens::{Screen, game_over::GameOverData},
 };
 
-mod bar;
+pub mod achievements;
+pub mod bar;
+pub mod death_freeze;
 pub mod game_sequencer;
-mod guide;
+#[cfg(feature = "dev")]
+pub mod god_mode;
+pub mod guide;
 mod juice;
+pub mod layout;
 mod mechanics;
-mod player;
+pub mod player;
+pub mod practice;
+mod soundtrack;
 
 pub(super) fn plugin(app: &mut App) {
-    app.load_resource::<Soundtrack>()
-        .add_plugins((
-            game_sequencer::plugin,
-            guide::plugin,
-            player::plugin,
-            bar::plugin,
-            mechanics::plugin,
-            juice::plugin,
-        ))
-        .add_systems(OnEnter(Screen::Gameplay), start_soundtrack)
-        .add_systems(OnEnter(GameMechanic::Victory), on_victory)
-        .add_observer(on_game_over);
+    app.add_plugins((
+        achievements::plugin,
+        game_sequencer::plugin,
+        guide::plugin,
+        player::plugin,
+        practice::plugin,
+        bar::plugin,
+        death_freeze::plugin,
+        mechanics::plugin,
+        juice::plugin,
+        layout::plugin,
+        soundtrack::plugin,
+    ))
+    .add_systems(OnEnter(GameMechanic::Victory), on_victory)
+    .add_observer(on_game_over);
+
+    #[cfg(feature = "dev")]
+    app.add_plugins(god_mode::plugin);
 }
 
 #[derive(Event)]
-pub struct GameOver(GameMechanic);
+pub struct GameOver {
+    pub cause: GameMechanic,
+    /// Entity which ended the run, e.g. the triangle which reached THE BUTTON.
+    pub culprit: Option<Entity>,
+    pub position: Option<Vec2>,
+    /// Run time shown by the timer, if it was already running.
+    pub elapsed_time: Option<f32>,
+    pub bars: Vec<BarSnapshot>,
+}
 
-#[derive(Resource, Asset, Clone, Reflect)]
-struct Soundtrack(Handle<AudioSource>);
+/// State of a bar at the moment of the game over.
+pub struct BarSnapshot {
+    pub name: String,
+    pub current: f32,
+    pub progress: f32,
+}
+
+/// Collects the context of a [`GameOver`].
+#[derive(SystemParam)]
+pub struct GameOverContext<'w, 's> {
+    elapsed_time: Option<Res<'w, ElapsedTime>>,
+    bars: Query<'w, 's, (&'static Bar, Option<&'static Name>)>,
+    transforms: Query<'w, 's, &'static GlobalTransform>,
+}
 
-impl FromWorld for Soundtrack {
-    fn from_world(world: &mut World) -> Self {
-        let assets = world.resource::<AssetServer>();
-        Self(assets.load("audio\\music\\soundtrack.ogg"))
+impl GameOverContext<'_, '_> {
+    pub fn game_over(&self, cause: GameMechanic, culprit: Option<Entity>) -> GameOver {
+        GameOver {
+            cause,
+            culprit,
+            position: culprit
+                .and_then(|culprit| self.transforms.get(culprit).ok())
+                .map(|transform| transform.translation().xy()),
+            elapsed_time: self
+                .elapsed_time
+                .as_ref()
+                .map(|elapsed_time| elapsed_time.0),
+            bars: self
+                .bars
+                .iter()
+                .map(|(bar, name)| BarSnapshot {
+                    name: name.map_or("Bar", Name::as_str).to_string(),
+                    current: bar.current,
+                    progress: bar.progress(),
+                })
+                .collect(),
+        }
     }
 }
 
 fn on_game_over(
     trigger: Trigger<GameOver>,
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
     mut game_over_data: ResMut<GameOverData>,
     mut next_screen: ResMut<NextState<Screen>>,
+    freeze: Option<Res<DeathFreeze>>,
+    #[cfg(feature = "dev")] mut god_mode: ResMut<god_mode::GodMode>,
+    #[cfg(feature = "dev")] time: Res<Time<Real>>,
 ) {
-    if trigger.event().0 != GameMechanic::Victory {
-        let handle = asset_server.load("audio/sound_effects/lose.ogg");
-        commands.spawn((Name::new("Lose sound"), sound_effect(handle, 0.4)));
+    let event = trigger.event();
+    // The run is already over.
+    if freeze.is_some() {
+        return;
     }
 
-    game_over_data.reason = trigger.event().0;
-    next_screen.set(Screen::GameOver);
-}
+    #[cfg(feature = "dev")]
+    if god_mode.absorb(event.cause, time.elapsed_secs()) {
+        return;
+    }
+
+    if event.cause == GameMechanic::Victory {
+        game_over_data.reason = event.cause;
+        next_screen.set(Screen::GameOver);
+        return;
+    }
 
-fn start_soundtrack(mut commands: Commands, soundtrack: Res<Soundtrack>) {
-    commands.spawn((
-        Name::new("Soundtrack"),
-        StateScoped(Screen::Gameplay),
-        music(soundtrack.0.clone(), 0.8),
-    ));
+    commands.trigger(PlayStinger("lose"));
+    commands.insert_resource(DeathFreeze::new(event.cause, event.culprit));
 }
 
-fn on_victory(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let handle = asset_server.load("audio/sound_effects/victory.ogg");
-    commands.spawn((Name::new("Victory sound"), sound_effect(handle, 0.3)));
-    commands.trigger(GameOver(GameMechanic::Victory));
+fn on_victory(mut commands: Commands, game_over_context: GameOverContext) {
+    commands.trigger(PlayStinger("victory"));
+    commands.trigger(game_over_context.game_over(GameMechanic::Victory, None));
 }
@@ -2,8 +2,12 @@ use bevy::{prelude::*, window::PrimaryWindow};
 
 use crate::{
     PausableSystems,
-    audio::sound_effect,
-    game::juice::{circles::SpawnCircles, pulse_effect::PulseEffect},
+    audio::sfx::PlaySfx,
+    game::{
+        juice::{circles::SpawnCircles, pulse_effect::PulseEffect},
+        layout::FillViewport,
+    },
+    playfield::PLAYFIELD_Z,
     screens::Screen,
 };
 
@@ -18,6 +22,10 @@ pub(super) fn plugin(app: &mut App) {
         .add_systems(Update, move_player.in_set(PausableSystems));
 }
 
+/// Invisible mesh behind everything, catching clicks that miss every target.
+#[derive(Component)]
+pub struct ClickCatcher;
+
 #[derive(Component, Default)]
 pub struct Player {
     pub clicked_on_target: bool,
@@ -41,6 +49,7 @@ fn spawn_player(
             min: 0.9,
             max: 1.1,
             speed: 0.5,
+            ..default()
         },
         Player::default(),
         StateScoped(Screen::Gameplay),
@@ -53,10 +62,13 @@ fn spawn_player(
     // spawn non-target click mesh
     commands
         .spawn((
-            Mesh2d(meshes.add(Rectangle::new(window.width(), window.height()))),
+            Mesh2d(meshes.add(Rectangle::new(1.0, 1.0))),
             MeshMaterial2d(materials.add(Color::linear_rgba(0.0, 0.0, 0.0, 0.0))),
             StateScoped(Screen::Gameplay),
-            Transform::from_xyz(0.0, 0.0, -1000.0),
+            // just in front of the playfield
+            Transform::from_xyz(0.0, 0.0, PLAYFIELD_Z + 1.0),
+            FillViewport,
+            ClickCatcher,
         ))
         .observe(create_click_effect);
 }
@@ -81,13 +93,8 @@ fn show_cursor(mut window: Single<&mut Window, With<PrimaryWindow>>) {
     window.cursor_options.visible = true;
 }
 
-fn create_click_effect(
-    trigger: Trigger<Pointer<Click>>,
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-) {
-    let handle = asset_server.load("audio/sound_effects/click.ogg");
-    commands.spawn((Name::new("Cursor click sound"), sound_effect(handle, 0.1)));
+fn create_click_effect(trigger: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(PlaySfx("click"));
     commands.trigger(SpawnCircles {
         location: trigger.hit.position.unwrap().xy().extend(CLICK_PARTICLES_Z),
         ..default()
@@ -0,0 +1,88 @@
+//! Practice mode: a sandbox run with a single mechanic and its prerequisites, without the
+//! sequencer.
+
+use bevy::prelude::*;
+
+use crate::{game::game_sequencer::GameMechanic, screens::Screen};
+
+/// Range of each of the [`PracticeSettings`], relative to the normal game.
+pub const MIN_PRACTICE_SPEED: f32 = 0.25;
+pub const MAX_PRACTICE_SPEED: f32 = 2.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PracticeRun>()
+        .init_resource::<PracticeSettings>()
+        .init_resource::<ThreatTuning>()
+        .add_systems(OnEnter(Screen::Gameplay), apply_practice_settings)
+        .add_systems(OnExit(Screen::Gameplay), reset_speed);
+}
+
+/// The mechanic being practiced. When set, runs don't follow the action sequence.
+#[derive(Resource, Default)]
+pub struct PracticeRun(pub Option<GameMechanic>);
+
+#[derive(Resource)]
+pub struct PracticeSettings {
+    /// Relative speed of the game.
+    pub speed: f32,
+    /// Relative time between spawns of triangles and squares.
+    pub spawn_interval: f32,
+    /// Relative speed of triangles, squares and the pentagon.
+    pub threat_speed: f32,
+}
+
+impl Default for PracticeSettings {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            spawn_interval: 1.0,
+            threat_speed: 1.0,
+        }
+    }
+}
+
+/// How the threats of the current run are tuned, from the [`PracticeSettings`] in practice runs
+/// and unchanged otherwise.
+#[derive(Resource)]
+pub struct ThreatTuning {
+    pub spawn_interval: f32,
+    pub speed: f32,
+}
+
+impl Default for ThreatTuning {
+    fn default() -> Self {
+        Self {
+            spawn_interval: 1.0,
+            speed: 1.0,
+        }
+    }
+}
+
+/// Mechanics which can be practiced.
+pub fn practice_mechanics() -> impl Iterator<Item = GameMechanic> {
+    use strum::IntoEnumIterator;
+
+    GameMechanic::iter().filter(|m| !matches!(m, GameMechanic::None | GameMechanic::Victory))
+}
+
+fn apply_practice_settings(
+    mut commands: Commands,
+    practice: Res<PracticeRun>,
+    settings: Res<PracticeSettings>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if practice.0.is_none() {
+        commands.insert_resource(ThreatTuning::default());
+        return;
+    }
+
+    time.set_relative_speed(settings.speed);
+    commands.insert_resource(ThreatTuning {
+        spawn_interval: settings.spawn_interval,
+        speed: settings.threat_speed,
+    });
+}
+
+fn reset_speed(mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(1.0);
+}
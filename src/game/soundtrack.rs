@@ -0,0 +1,451 @@
+//! The gameplay soundtrack. It is split into layers defined in
+//! `assets/audio/music/soundtrack.layers`, which all play in sync from the start of the run.
+//! Layers tied to a mechanic stay silent until that mechanic is entered, then fade in from the
+//! next bar. Stingers play on the next beat when the run ends, while the layers fade out.
+//!
+//! The optional tempo map of the soundtrack drives the [`BeatClock`] during the run.
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    audio::{PlaybackMode, Volume},
+    prelude::*,
+};
+use thiserror::Error;
+
+use crate::{
+    asset_tracking::LoadResource,
+    audio::{
+        Gain, Music, PausableAudio, apply_volumes,
+        beat_clock::{BeatClock, TempoMap, tick_beat_clock},
+        stem::Stem,
+        synth::Synth,
+    },
+    game::game_sequencer::GameMechanic,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<SoundtrackLayers>()
+        .init_asset_loader::<SoundtrackLayersLoader>()
+        .load_resource::<SoundtrackAssets>()
+        .init_resource::<PendingStingers>()
+        .add_observer(queue_stinger)
+        .add_systems(OnEnter(Screen::Gameplay), spawn_layers)
+        .add_systems(
+            Update,
+            (activate_layers, fade_layers, play_stingers)
+                .chain()
+                .after(tick_beat_clock),
+        )
+        .add_systems(PostUpdate, start_layers.after(apply_volumes));
+}
+
+/// Beats of a layer's fade in, or of the fade out before a stinger at the latest.
+const FADE_BEATS: f32 = 2.0;
+
+/// Plays the stinger with the given name on the next beat of the soundtrack.
+#[derive(Event)]
+pub struct PlayStinger(pub &'static str);
+
+enum LayerSound {
+    Recording(Handle<AudioSource>),
+    Stem(Handle<Stem>),
+}
+
+struct LayerDefinition {
+    name: String,
+    sound: LayerSound,
+    volume: f32,
+    /// Mechanic which fades the layer in, layers without one always play.
+    mechanic: Option<GameMechanic>,
+}
+
+enum StingerSound {
+    Recording(Handle<AudioSource>),
+    /// A `.sfx` preset.
+    Synth(Handle<Synth>),
+}
+
+struct Stinger {
+    sound: StingerSound,
+    volume: f32,
+}
+
+/// Layers and stingers of the soundtrack, loaded from a `.layers` file.
+#[derive(Asset, TypePath)]
+struct SoundtrackLayers {
+    tempo: TempoMap,
+    layers: Vec<LayerDefinition>,
+    stingers: HashMap<String, Stinger>,
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct SoundtrackAssets {
+    #[dependency]
+    layers: Handle<SoundtrackLayers>,
+}
+
+impl FromWorld for SoundtrackAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            layers: assets.load("audio/music/soundtrack.layers"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SoundtrackLayersLoader;
+
+#[derive(Error, Debug)]
+enum SoundtrackLayersLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(
+        "Line {0}: expected `tempo | bpm | beats per bar | offset`, `beats | beats per bar | timestamps`, `layer | name | sound | volume | mechanic` or `stinger | name | sound | volume`."
+    )]
+    InvalidFormat(usize),
+    #[error("Line {0}: `{1}` is not a valid number.")]
+    InvalidNumber(usize, String),
+    #[error("Line {0}: unknown mechanic `{1}`.")]
+    InvalidMechanic(usize, String),
+    #[error("Line {0}: `{1}` is already defined.")]
+    DuplicateName(usize, String),
+    #[error("Line {0}: expected at least two increasing beat timestamps.")]
+    InvalidTimestamps(usize),
+    #[error("Line {0}: the tempo map is already defined.")]
+    DuplicateTempo(usize),
+}
+
+impl AssetLoader for SoundtrackLayersLoader {
+    type Asset = SoundtrackLayers;
+    type Settings = ();
+    type Error = SoundtrackLayersLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+
+        let mut tempo = None;
+        let mut layers: Vec<LayerDefinition> = Vec::new();
+        let mut stingers = HashMap::new();
+        for (line, l) in text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !(l.is_empty() || l.starts_with("#")))
+        {
+            let parts: Vec<&str> = l.split('|').map(|t| t.trim()).collect();
+            let number = |text: &str| {
+                text.parse::<f32>()
+                    .ok()
+                    .filter(|number| *number >= 0.0)
+                    .ok_or_else(|| SoundtrackLayersLoaderError::InvalidNumber(line, text.into()))
+            };
+
+            let beats_per_bar = |text: &str| {
+                text.parse::<u32>()
+                    .ok()
+                    .filter(|beats_per_bar| *beats_per_bar > 0)
+                    .ok_or_else(|| SoundtrackLayersLoaderError::InvalidNumber(line, text.into()))
+            };
+
+            if matches!(parts[0], "tempo" | "beats") && tempo.is_some() {
+                return Err(SoundtrackLayersLoaderError::DuplicateTempo(line));
+            }
+            match parts[..] {
+                ["tempo", bpm, bar] | ["tempo", bpm, bar, _] => {
+                    let bpm_value = number(bpm)?;
+                    if bpm_value == 0.0 {
+                        return Err(SoundtrackLayersLoaderError::InvalidNumber(line, bpm.into()));
+                    }
+                    tempo = Some(TempoMap::Constant {
+                        bpm: bpm_value,
+                        offset: parts.get(3).map_or(Ok(0.0), |offset| number(offset))?,
+                        beats_per_bar: beats_per_bar(bar)?,
+                    });
+                }
+                ["beats", bar, timestamps] => {
+                    let beats = timestamps
+                        .split_whitespace()
+                        .map(number)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if beats.len() < 2 || beats.windows(2).any(|pair| pair[1] <= pair[0]) {
+                        return Err(SoundtrackLayersLoaderError::InvalidTimestamps(line));
+                    }
+                    tempo = Some(TempoMap::Timestamps {
+                        beats,
+                        beats_per_bar: beats_per_bar(bar)?,
+                    });
+                }
+                ["layer", name, sound, volume, mechanic] => {
+                    if layers.iter().any(|layer| layer.name == name) {
+                        return Err(SoundtrackLayersLoaderError::DuplicateName(
+                            line,
+                            name.into(),
+                        ));
+                    }
+                    let mechanic = match mechanic {
+                        "-" => None,
+                        _ => Some(mechanic.parse().map_err(|_| {
+                            SoundtrackLayersLoaderError::InvalidMechanic(line, mechanic.into())
+                        })?),
+                    };
+                    let sound = match sound.ends_with(".stem") {
+                        true => LayerSound::Stem(load_context.load(sound)),
+                        false => LayerSound::Recording(load_context.load(sound)),
+                    };
+                    layers.push(LayerDefinition {
+                        name: name.into(),
+                        sound,
+                        volume: number(volume)?,
+                        mechanic,
+                    });
+                }
+                ["stinger", name, sound, volume] => {
+                    let stinger = Stinger {
+                        sound: match sound.ends_with(".sfx") {
+                            true => StingerSound::Synth(load_context.load(sound)),
+                            false => StingerSound::Recording(load_context.load(sound)),
+                        },
+                        volume: number(volume)?,
+                    };
+                    if stingers.insert(name.to_string(), stinger).is_some() {
+                        return Err(SoundtrackLayersLoaderError::DuplicateName(
+                            line,
+                            name.into(),
+                        ));
+                    }
+                }
+                _ => return Err(SoundtrackLayersLoaderError::InvalidFormat(line)),
+            }
+        }
+
+        Ok(SoundtrackLayers {
+            tempo: tempo.unwrap_or_default(),
+            layers,
+            stingers,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["layers"]
+    }
+}
+
+#[derive(Component)]
+struct SoundtrackLayer {
+    mechanic: Option<GameMechanic>,
+    fade: Fade,
+}
+
+/// Gain going from `from` to `to` over `duration` seconds of the [`BeatClock`].
+#[derive(Clone, Copy, Debug)]
+struct Fade {
+    from: f32,
+    to: f32,
+    start: f32,
+    duration: f32,
+}
+
+impl Fade {
+    fn constant(gain: f32) -> Self {
+        Self {
+            from: gain,
+            to: gain,
+            start: 0.0,
+            duration: 0.0,
+        }
+    }
+
+    fn gain(&self, time: f32) -> f32 {
+        let progress = match self.duration > 0.0 {
+            true => ((time - self.start) / self.duration).clamp(0.0, 1.0),
+            false => (time >= self.start) as u8 as f32,
+        };
+        self.from.lerp(self.to, progress)
+    }
+}
+
+/// Layers which are spawned paused, they are started together once all of them can play.
+#[derive(Component)]
+struct WaitingToStart;
+
+/// Stingers to play once the clock reaches their time.
+#[derive(Resource, Default)]
+struct PendingStingers(Vec<(&'static str, f32)>);
+
+fn spawn_layers(
+    mut commands: Commands,
+    soundtrack_assets: Res<SoundtrackAssets>,
+    soundtracks: Res<Assets<SoundtrackLayers>>,
+) {
+    let Some(soundtrack) = soundtracks.get(&soundtrack_assets.layers) else {
+        return;
+    };
+    // The clock restarts once the layers play, it already runs in case they never do, e.g.
+    // without an audio device.
+    commands.insert_resource(BeatClock::new(soundtrack.tempo.clone()));
+
+    for layer in &soundtrack.layers {
+        let settings = PlaybackSettings {
+            paused: true,
+            ..PlaybackSettings::LOOP
+        }
+        .with_volume(Volume::Linear(layer.volume));
+        let gain = match layer.mechanic {
+            Some(_) => 0.0,
+            None => 1.0,
+        };
+
+        let mut entity = commands.spawn((
+            Name::new(format!("Soundtrack layer {}", layer.name)),
+            StateScoped(Screen::Gameplay),
+            SoundtrackLayer {
+                mechanic: layer.mechanic,
+                fade: Fade::constant(gain),
+            },
+            Gain(gain),
+            Music,
+            PausableAudio,
+            WaitingToStart,
+        ));
+        match &layer.sound {
+            LayerSound::Recording(handle) => entity.insert((AudioPlayer(handle.clone()), settings)),
+            // Stems never end, looping would buffer them forever.
+            LayerSound::Stem(handle) => entity.insert((
+                AudioPlayer(handle.clone()),
+                PlaybackSettings {
+                    mode: PlaybackMode::Once,
+                    ..settings
+                },
+            )),
+        };
+    }
+}
+
+/// Starts all layers at once, after [`apply_volumes`] has set up their gain, and restarts the
+/// [`BeatClock`] with them.
+fn start_layers(
+    mut commands: Commands,
+    waiting: Query<(Entity, Option<&AudioSink>), With<WaitingToStart>>,
+    soundtrack_assets: Option<Res<SoundtrackAssets>>,
+    soundtracks: Res<Assets<SoundtrackLayers>>,
+) {
+    if waiting.is_empty() || waiting.iter().any(|(_, sink)| sink.is_none()) {
+        return;
+    }
+    let Some(soundtrack) =
+        soundtrack_assets.and_then(|soundtrack_assets| soundtracks.get(&soundtrack_assets.layers))
+    else {
+        return;
+    };
+
+    for (entity, sink) in &waiting {
+        sink.unwrap().play();
+        commands.entity(entity).remove::<WaitingToStart>();
+    }
+    commands.insert_resource(BeatClock::new(soundtrack.tempo.clone()));
+}
+
+fn activate_layers(
+    mut transitions: EventReader<StateTransitionEvent<GameMechanic>>,
+    mut layers: Query<(&mut SoundtrackLayer, Has<WaitingToStart>)>,
+    clock: Option<Res<BeatClock>>,
+) {
+    for entered in transitions
+        .read()
+        .filter_map(|transition| transition.entered)
+    {
+        for (mut layer, waiting) in &mut layers {
+            if layer.mechanic != Some(entered) {
+                continue;
+            }
+            // Layers of mechanics restored before the music starts play right away.
+            layer.fade = match clock.as_ref().filter(|_| !waiting) {
+                Some(clock) => Fade {
+                    from: layer.fade.gain(clock.elapsed()),
+                    to: 1.0,
+                    start: clock.next_bar(),
+                    duration: FADE_BEATS * clock.beat_duration(),
+                },
+                None => Fade::constant(1.0),
+            };
+        }
+    }
+}
+
+fn fade_layers(mut layers: Query<(&SoundtrackLayer, &mut Gain)>, clock: Option<Res<BeatClock>>) {
+    let time = clock.map_or(0.0, |clock| clock.elapsed());
+    for (layer, mut gain) in &mut layers {
+        gain.set_if_neq(Gain(layer.fade.gain(time)));
+    }
+}
+
+/// Schedules the stinger on the next beat and fades the layers out until then.
+fn queue_stinger(
+    trigger: Trigger<PlayStinger>,
+    mut pending: ResMut<PendingStingers>,
+    mut layers: Query<&mut SoundtrackLayer>,
+    clock: Option<Res<BeatClock>>,
+) {
+    let Some(clock) = clock else {
+        pending.0.push((trigger.event().0, 0.0));
+        return;
+    };
+
+    let at = clock.next_beat();
+    for mut layer in &mut layers {
+        layer.fade = Fade {
+            from: layer.fade.gain(clock.elapsed()),
+            to: 0.0,
+            start: clock.elapsed(),
+            duration: (at - clock.elapsed()).min(FADE_BEATS * clock.beat_duration()),
+        };
+    }
+    pending.0.push((trigger.event().0, at));
+}
+
+fn play_stingers(
+    mut commands: Commands,
+    mut pending: ResMut<PendingStingers>,
+    clock: Option<Res<BeatClock>>,
+    soundtrack_assets: Option<Res<SoundtrackAssets>>,
+    soundtracks: Res<Assets<SoundtrackLayers>>,
+) {
+    let time = clock.map_or(f32::INFINITY, |clock| clock.elapsed());
+    let Some(soundtrack) =
+        soundtrack_assets.and_then(|soundtrack_assets| soundtracks.get(&soundtrack_assets.layers))
+    else {
+        return;
+    };
+
+    pending.0.retain(|(name, at)| {
+        if time < *at {
+            return true;
+        }
+        let Some(stinger) = soundtrack.stingers.get(*name) else {
+            warn!("Unknown stinger `{name}`.");
+            return false;
+        };
+        // Not scoped to the gameplay, so the stinger continues on the game over screen.
+        let mut entity = commands.spawn((
+            Name::new(format!("Stinger {name}")),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(stinger.volume)),
+            Music,
+        ));
+        match &stinger.sound {
+            StingerSound::Recording(handle) => entity.insert(AudioPlayer(handle.clone())),
+            StingerSound::Synth(handle) => entity.insert(AudioPlayer(handle.clone())),
+        };
+        false
+    });
+}
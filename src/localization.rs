@@ -0,0 +1,254 @@
+//! Localization. Player-facing strings live in string tables, one `assets/lang/<code>.lang`
+//! file per language. Any text starting with `@` is a key into these tables, e.g. `@menu.play`,
+//! other texts are shown as they are. Use `@@` for a literal leading `@`.
+//!
+//! Texts spawned with a [`LocalizedText`] are translated again whenever the [`Language`] changes.
+
+use std::collections::HashMap;
+
+use bevy::{
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use thiserror::Error;
+
+use crate::{
+    asset_tracking::LoadResource,
+    theme::markup::{self, Markup, MarkupError},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Language>()
+        .init_asset::<StringTable>()
+        .init_asset_loader::<StringTableLoader>()
+        .load_resource::<LanguageAssets>()
+        .add_systems(Update, update_localized_texts);
+}
+
+/// Codes of the available languages, in the order the language picker cycles through them.
+/// The first one is the default and is used for keys missing in other languages.
+pub const LANGUAGES: [&str; 2] = ["en", "cs"];
+
+/// Key of each table's own name, shown in the language picker.
+const LANGUAGE_NAME_KEY: &str = "language.name";
+
+/// The language all texts are shown in.
+#[derive(Resource, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Language(usize);
+
+impl Language {
+    pub fn code(self) -> &'static str {
+        LANGUAGES[self.0]
+    }
+
+    pub fn next(self) -> Self {
+        Self((self.0 + 1) % LANGUAGES.len())
+    }
+
+    pub fn previous(self) -> Self {
+        Self((self.0 + LANGUAGES.len() - 1) % LANGUAGES.len())
+    }
+}
+
+/// Keys and their texts in one language, loaded from a `.lang` file.
+#[derive(Asset, TypePath)]
+pub struct StringTable(HashMap<String, String>);
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct LanguageAssets {
+    /// One table per entry of [`LANGUAGES`].
+    #[dependency]
+    tables: Vec<Handle<StringTable>>,
+}
+
+impl FromWorld for LanguageAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            tables: LANGUAGES
+                .iter()
+                .map(|code| assets.load(format!("lang/{code}.lang")))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct StringTableLoader;
+
+#[derive(Error, Debug)]
+enum StringTableLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {0}: expected `key | text`.")]
+    InvalidFormat(usize),
+    #[error("Line {0}: key `{1}` is already defined.")]
+    DuplicateKey(usize, String),
+    #[error("Line {0}: {1}.")]
+    InvalidMarkup(usize, MarkupError),
+}
+
+impl AssetLoader for StringTableLoader {
+    type Asset = StringTable;
+    type Settings = ();
+    type Error = StringTableLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+
+        let mut table = HashMap::new();
+        for (line, l) in text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !(l.is_empty() || l.starts_with("#")))
+        {
+            let (key, text) = l
+                .split_once('|')
+                .map(|(key, text)| (key.trim(), text.trim().replace("\\n", "\n")))
+                .filter(|(key, _)| !key.is_empty())
+                .ok_or(StringTableLoaderError::InvalidFormat(line))?;
+
+            markup::parse(&text)
+                .map_err(|error| StringTableLoaderError::InvalidMarkup(line, error))?;
+            if table.insert(key.to_string(), text).is_some() {
+                return Err(StringTableLoaderError::DuplicateKey(line, key.into()));
+            }
+        }
+
+        Ok(StringTable(table))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lang"]
+    }
+}
+
+/// Text which is translated through the string tables. `{name}` placeholders are replaced by
+/// the arguments, which are translated as well.
+#[derive(Component, Clone, Default, PartialEq, Debug)]
+pub struct LocalizedText {
+    text: String,
+    args: Vec<(String, String)>,
+}
+
+impl LocalizedText {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.args.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl From<String> for LocalizedText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Looks up texts in the string table of the current language.
+#[derive(SystemParam)]
+pub struct Localization<'w> {
+    language: Res<'w, Language>,
+    language_assets: Option<Res<'w, LanguageAssets>>,
+    tables: Res<'w, Assets<StringTable>>,
+}
+
+impl Localization<'_> {
+    pub fn language(&self) -> Language {
+        *self.language
+    }
+
+    /// Translates `@key` texts, other texts are returned as they are. Missing keys fall back to
+    /// the default language, then to the key itself.
+    pub fn translate(&self, text: &str) -> String {
+        match text.strip_prefix('@') {
+            Some(escaped) if escaped.starts_with('@') => escaped.to_string(),
+            Some(key) => self
+                .lookup(*self.language, key)
+                .or_else(|| self.lookup(Language::default(), key))
+                .unwrap_or(text)
+                .to_string(),
+            None => text.to_string(),
+        }
+    }
+
+    pub fn resolve(&self, text: &LocalizedText) -> String {
+        let mut resolved = self.translate(&text.text);
+        for (name, value) in &text.args {
+            resolved = resolved.replace(&format!("{{{name}}}"), &self.translate(value));
+        }
+        resolved
+    }
+
+    /// The name of the language in that language, e.g. `Čeština`.
+    pub fn language_name(&self, language: Language) -> String {
+        self.lookup(language, LANGUAGE_NAME_KEY)
+            .unwrap_or(language.code())
+            .to_string()
+    }
+
+    /// Whether the language changed or the string tables were just loaded.
+    fn is_changed(&self) -> bool {
+        self.language.is_changed()
+            || self
+                .language_assets
+                .as_ref()
+                .is_some_and(|assets| assets.is_added())
+    }
+
+    fn lookup(&self, language: Language, key: &str) -> Option<&str> {
+        let handle = self.language_assets.as_ref()?.tables.get(language.0)?;
+        self.tables.get(handle)?.0.get(key).map(String::as_str)
+    }
+}
+
+/// Writes translated texts into the [`Markup`], [`Text`] or [`Text2d`] of their entity.
+pub fn update_localized_texts(
+    mut texts: Query<(
+        Ref<LocalizedText>,
+        Option<&mut Markup>,
+        Option<&mut Text>,
+        Option<&mut Text2d>,
+    )>,
+    localization: Localization,
+    mut table_events: EventReader<AssetEvent<StringTable>>,
+) {
+    // Everything is translated again when the language or one of the tables changes.
+    let update_all = localization.is_changed() || table_events.read().count() > 0;
+
+    for (text, markup, plain_text, text_2d) in &mut texts {
+        if !update_all && !text.is_changed() {
+            continue;
+        }
+
+        let resolved = localization.resolve(&text);
+        if let Some(mut markup) = markup {
+            *markup = Markup::new(resolved);
+        } else if let Some(mut plain_text) = plain_text {
+            plain_text.0 = resolved;
+        } else if let Some(mut text_2d) = text_2d {
+            text_2d.0 = resolved;
+        }
+    }
+}
@@ -8,13 +8,18 @@ mod audio;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod game;
+mod localization;
 mod menus;
+mod playfield;
 mod screens;
+mod telemetry;
 mod theme;
 
-use bevy::{asset::AssetMetaCheck, prelude::*};
+use bevy::{asset::AssetMetaCheck, prelude::*, window::PrimaryWindow};
 use bevy_simple_subsecond_system::prelude::*;
 
+use crate::playfield::PlayfieldScaling;
+
 fn main() -> AppExit {
     App::new().add_plugins(AppPlugin).run()
 }
@@ -50,8 +55,11 @@ impl Plugin for AppPlugin {
             game::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
+            localization::plugin,
             menus::plugin,
+            playfield::plugin,
             screens::plugin,
+            telemetry::plugin,
             theme::plugin,
         ));
 
@@ -94,6 +102,15 @@ struct Pause(pub bool);
 #[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
 struct PausableSystems;
 
-fn spawn_camera(mut commands: Commands) {
-    commands.spawn((Name::new("Camera"), Camera2d));
+fn spawn_camera(
+    mut commands: Commands,
+    scaling: Res<PlayfieldScaling>,
+    window: Single<&Window, With<PrimaryWindow>>,
+) {
+    commands.spawn((
+        Name::new("Camera"),
+        Camera2d,
+        scaling.projection(window.size()),
+        audio::spatial::listener(),
+    ));
 }
@@ -0,0 +1,114 @@
+//! The achievements menu.
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    game::achievements::{AchievementAssets, AchievementList, AchievementProgress},
+    localization::LocalizedText,
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Achievements), spawn_achievements_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Achievements).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_achievements_menu(
+    mut commands: Commands,
+    achievement_assets: Res<AchievementAssets>,
+    achievement_lists: Res<Assets<AchievementList>>,
+    progress: Res<AchievementProgress>,
+) {
+    let rows = achievement_lists
+        .get(&achievement_assets.list)
+        .map(|list| {
+            list.0
+                .iter()
+                .map(|achievement| {
+                    let record = progress.0.get(&achievement.id).copied().unwrap_or_default();
+                    let status = if record.unlocked {
+                        "@achievements.unlocked".into()
+                    } else if achievement.condition.is_counter() {
+                        format!("{:.0}/{:.0}", record.best.floor(), achievement.target).into()
+                    } else {
+                        "@achievements.locked".into()
+                    };
+
+                    [
+                        LocalizedText::new("{title}\n{description}")
+                            .with_arg("title", &achievement.title)
+                            .with_arg("description", &achievement.description),
+                        status,
+                    ]
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    commands.spawn((
+        widget::ui_root("Achievements Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Achievements),
+        children![
+            widget::header("@menu.achievements"),
+            grid(rows),
+            widget::button("@menu.back", go_back_on_click),
+        ],
+    ));
+}
+
+fn grid(content: Vec<[LocalizedText; 2]>) -> impl Bundle {
+    (
+        Name::new("Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: vec![GridTrack::px(600.0), GridTrack::px(200.0)],
+            ..default()
+        },
+        Children::spawn(SpawnIter(content.into_iter().flatten().enumerate().map(
+            |(i, text)| {
+                (
+                    widget::label(text),
+                    Node {
+                        justify_self: if i.is_multiple_of(2) {
+                            JustifySelf::Start
+                        } else {
+                            JustifySelf::End
+                        },
+                        align_self: AlignSelf::Center,
+                        ..default()
+                    },
+                )
+            },
+        ))),
+    )
+}
+
+fn go_back_on_click(
+    _: Trigger<Pointer<Click>>,
+    screen: Res<State<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
+
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(if screen.get() == &Screen::Title {
+        Menu::Main
+    } else {
+        Menu::Pause
+    });
+}
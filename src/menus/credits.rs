@@ -24,11 +24,11 @@ fn spawn_credits_menu(mut commands: Commands) {
         GlobalZIndex(2),
         StateScoped(Menu::Credits),
         children![
-            widget::header("Created by"),
+            widget::header("@credits.created_by"),
             created_by(),
-            widget::header("Assets"),
+            widget::header("@credits.assets"),
             assets(),
-            widget::button("Back", go_back_on_click),
+            widget::button("@menu.back", go_back_on_click),
         ],
     ));
 }
@@ -45,6 +45,10 @@ fn assets() -> impl Bundle {
             "Supper Vanilla font",
             "Free for personal use & commercial use by  fsuarez91",
         ],
+        [
+            "DejaVu Sans font",
+            "Bitstream Vera license by the DejaVu fonts team",
+        ],
         [
             "Bevy logo",
             "All rights reserved by the Bevy Foundation, permission granted for splash screen use when unmodified",
@@ -2,7 +2,10 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    asset_tracking::ResourceHandles, game::practice::PracticeRun, menus::Menu, screens::Screen,
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
@@ -15,16 +18,20 @@ fn spawn_main_menu(mut commands: Commands) {
         StateScoped(Menu::Main),
         #[cfg(not(target_family = "wasm"))]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
-            widget::button("Exit", exit_app),
+            widget::button("@menu.play", enter_loading_or_gameplay_screen),
+            widget::button("@menu.practice", open_practice_menu),
+            widget::button("@menu.settings", open_settings_menu),
+            widget::button("@menu.achievements", open_achievements_menu),
+            widget::button("@menu.credits", open_credits_menu),
+            widget::button("@menu.exit", exit_app),
         ],
         #[cfg(target_family = "wasm")]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Credits", open_credits_menu),
+            widget::button("@menu.play", enter_loading_or_gameplay_screen),
+            widget::button("@menu.practice", open_practice_menu),
+            widget::button("@menu.settings", open_settings_menu),
+            widget::button("@menu.achievements", open_achievements_menu),
+            widget::button("@menu.credits", open_credits_menu),
         ],
     ));
 }
@@ -32,8 +39,10 @@ fn spawn_main_menu(mut commands: Commands) {
 fn enter_loading_or_gameplay_screen(
     _: Trigger<Pointer<Click>>,
     resource_handles: Res<ResourceHandles>,
+    mut practice: ResMut<PracticeRun>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
+    practice.0 = None;
     if resource_handles.is_all_done() {
         next_screen.set(Screen::Gameplay);
     } else {
@@ -41,10 +50,18 @@ fn enter_loading_or_gameplay_screen(
     }
 }
 
+fn open_practice_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Practice);
+}
+
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
 
+fn open_achievements_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Achievements);
+}
+
 fn open_credits_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Credits);
 }
@@ -1,8 +1,10 @@
 //! The game's menus and transitions between them.
 
+mod achievements;
 mod credits;
 mod main;
 mod pause;
+mod practice;
 mod settings;
 
 use bevy::prelude::*;
@@ -11,10 +13,12 @@ pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
+        achievements::plugin,
         credits::plugin,
         main::plugin,
         settings::plugin,
         pause::plugin,
+        practice::plugin,
     ));
 }
 
@@ -27,4 +31,6 @@ pub enum Menu {
     Credits,
     Settings,
     Pause,
+    Achievements,
+    Practice,
 }
@@ -1,36 +1,152 @@
 //! The pause menu.
 
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{
+    ecs::spawn::SpawnIter,
+    input::{
+        common_conditions::input_just_pressed,
+        mouse::{MouseScrollUnit, MouseWheel},
+    },
+    prelude::*,
+    ui::Val::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    game::{
+        game_sequencer::{LastCheckpoint, RestartFromCheckpoint},
+        guide::GuideHistory,
+    },
+    localization::LocalizedText,
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Pause).and(input_just_pressed(KeyCode::Escape))),
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+            scroll_guide_history,
+        )
+            .run_if(in_state(Menu::Pause)),
     );
 }
 
-fn spawn_pause_menu(mut commands: Commands) {
-    commands.spawn((
-        widget::ui_root("Pause Menu"),
-        GlobalZIndex(2),
-        StateScoped(Menu::Pause),
+/// Pixels scrolled per line of mouse wheel movement.
+const SCROLL_LINE_HEIGHT: f32 = 32.0;
+
+#[derive(Component)]
+struct GuideHistoryPanel;
+
+fn spawn_pause_menu(
+    mut commands: Commands,
+    last_checkpoint: Res<LastCheckpoint>,
+    guide_history: Res<GuideHistory>,
+) {
+    commands
+        .spawn((
+            widget::ui_root("Pause Menu"),
+            GlobalZIndex(2),
+            StateScoped(Menu::Pause),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("@pause.title"));
+            parent
+                .spawn((
+                    Name::new("Pause Menu Content"),
+                    Node {
+                        column_gap: Px(40.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            Name::new("Pause Menu Buttons"),
+                            Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Px(20.0),
+                                ..default()
+                            },
+                        ))
+                        .with_children(|parent| spawn_buttons(parent, &last_checkpoint));
+                    parent.spawn(guide_history_panel(&guide_history));
+                });
+        });
+}
+
+fn spawn_buttons(parent: &mut ChildSpawnerCommands, last_checkpoint: &LastCheckpoint) {
+    parent.spawn(widget::button("@pause.continue", close_menu));
+    parent.spawn(widget::button("@menu.settings", open_settings_menu));
+    parent.spawn(widget::button("@menu.achievements", open_achievements_menu));
+    parent.spawn(widget::button("@pause.restart", restart));
+    if let Some(checkpoint) = &last_checkpoint.0 {
+        parent.spawn(widget::button(
+            LocalizedText::new("@pause.restart_from").with_arg("checkpoint", &checkpoint.name),
+            restart_from_checkpoint,
+        ));
+    }
+    parent.spawn(widget::button("@menu.exit", exit));
+}
+
+fn guide_history_panel(guide_history: &GuideHistory) -> impl Bundle {
+    // newest first, so the last thing the guide said is right at the top
+    let mut messages: Vec<_> = guide_history.0.iter().rev().cloned().collect();
+    if messages.is_empty() {
+        messages.push("@pause.guide_history_empty".to_string());
+    }
+
+    (
+        Name::new("Guide History"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Px(10.0),
+            width: Px(500.0),
+            ..default()
+        },
         children![
-            widget::header("Game paused"),
-            widget::button("Continue", close_menu),
-            widget::button("Settings", open_settings_menu),
-            widget::button("Restart", restart),
-            widget::button("Exit", exit),
+            widget::label("@pause.guide_history"),
+            (
+                Name::new("Guide History Messages"),
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Px(12.0),
+                    height: Px(440.0),
+                    padding: UiRect::all(Px(12.0)),
+                    overflow: Overflow::scroll_y(),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.4)),
+                GuideHistoryPanel,
+                Children::spawn(SpawnIter(messages.into_iter().map(widget::label))),
+            ),
         ],
-    ));
+    )
+}
+
+fn scroll_guide_history(
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut panel: Single<&mut ScrollPosition, With<GuideHistoryPanel>>,
+) {
+    for event in mouse_wheel.read() {
+        let delta = match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        // the layout clamps the other end to the content size
+        panel.offset_y = (panel.offset_y - delta).max(0.0);
+    }
 }
 
 fn open_settings_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
 
+fn open_achievements_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Achievements);
+}
+
 fn close_menu(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
@@ -43,6 +159,10 @@ fn restart(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>
     next_screen.set(Screen::Loading);
 }
 
+fn restart_from_checkpoint(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(RestartFromCheckpoint);
+}
+
 fn exit(_: Trigger<Pointer<Click>>, mut app_exit_ew: EventWriter<AppExit>) {
     app_exit_ew.write(AppExit::Success);
 }
@@ -0,0 +1,172 @@
+//! The practice menu.
+
+use bevy::{
+    ecs::spawn::SpawnIter, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*,
+};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::practice::{
+        MAX_PRACTICE_SPEED, MIN_PRACTICE_SPEED, PracticeRun, PracticeSettings, practice_mechanics,
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+/// Change of a setting per click.
+const SETTING_STEP: f32 = 0.25;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Practice), spawn_practice_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::Practice).and(input_just_pressed(KeyCode::Escape))),
+    );
+
+    app.register_type::<SettingLabel>();
+    app.add_systems(
+        Update,
+        update_setting_labels.run_if(in_state(Menu::Practice)),
+    );
+}
+
+fn spawn_practice_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Practice Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Practice),
+        children![
+            widget::header("@menu.practice"),
+            mechanics_grid(),
+            setting_widget(PracticeSetting::Speed),
+            setting_widget(PracticeSetting::SpawnInterval),
+            setting_widget(PracticeSetting::ThreatSpeed),
+            widget::button("@menu.back", go_back_on_click),
+        ],
+    ));
+}
+
+fn mechanics_grid() -> impl Bundle {
+    (
+        Name::new("Mechanics Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 380.0),
+            ..default()
+        },
+        Children::spawn(SpawnIter(practice_mechanics().map(|mechanic| {
+            widget::button(
+                format!(
+                    "@practice.mechanic.{}",
+                    mechanic.as_ref().to_lowercase().replace(' ', "_")
+                ),
+                move |_: Trigger<Pointer<Click>>,
+                      mut practice: ResMut<PracticeRun>,
+                      resource_handles: Res<ResourceHandles>,
+                      mut next_screen: ResMut<NextState<Screen>>| {
+                    practice.0 = Some(mechanic);
+                    next_screen.set(if resource_handles.is_all_done() {
+                        Screen::Gameplay
+                    } else {
+                        Screen::Loading
+                    });
+                },
+            )
+        }))),
+    )
+}
+
+/// A setting of the [`PracticeSettings`], adjusted in steps within the practice range.
+#[derive(Clone, Copy, Reflect)]
+enum PracticeSetting {
+    Speed,
+    SpawnInterval,
+    ThreatSpeed,
+}
+
+impl PracticeSetting {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Speed => "@practice.speed",
+            Self::SpawnInterval => "@practice.spawn_interval",
+            Self::ThreatSpeed => "@practice.threat_speed",
+        }
+    }
+
+    fn value(self, settings: &PracticeSettings) -> f32 {
+        match self {
+            Self::Speed => settings.speed,
+            Self::SpawnInterval => settings.spawn_interval,
+            Self::ThreatSpeed => settings.threat_speed,
+        }
+    }
+
+    fn value_mut(self, settings: &mut PracticeSettings) -> &mut f32 {
+        match self {
+            Self::Speed => &mut settings.speed,
+            Self::SpawnInterval => &mut settings.spawn_interval,
+            Self::ThreatSpeed => &mut settings.threat_speed,
+        }
+    }
+}
+
+fn setting_widget(setting: PracticeSetting) -> impl Bundle {
+    (
+        Name::new("Setting Widget"),
+        Node {
+            column_gap: Px(10.0),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![
+            widget::label(setting.label()),
+            widget::button_small(
+                "-",
+                move |_: Trigger<Pointer<Click>>, mut settings: ResMut<PracticeSettings>| {
+                    let value = setting.value_mut(&mut settings);
+                    *value = (*value - SETTING_STEP).max(MIN_PRACTICE_SPEED);
+                },
+            ),
+            (
+                Name::new("Current Value"),
+                Node {
+                    width: Px(80.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), SettingLabel(setting))],
+            ),
+            widget::button_small(
+                "+",
+                move |_: Trigger<Pointer<Click>>, mut settings: ResMut<PracticeSettings>| {
+                    let value = setting.value_mut(&mut settings);
+                    *value = (*value + SETTING_STEP).min(MAX_PRACTICE_SPEED);
+                },
+            ),
+        ],
+    )
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SettingLabel(PracticeSetting);
+
+fn update_setting_labels(
+    settings: Res<PracticeSettings>,
+    mut labels: Query<(&mut Text, &SettingLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        text.0 = format!("{:.2}x", label.0.value(&settings));
+    }
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
@@ -2,9 +2,20 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
+use bevy::{
+    audio::Volume, ecs::system::IntoObserverSystem, input::common_conditions::input_just_pressed,
+    prelude::*, ui::Val::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    audio::CategoryVolumes,
+    localization::{Language, Localization, LocalizedText},
+    menus::Menu,
+    playfield::PlayfieldScaling,
+    screens::Screen,
+    telemetry::TelemetrySettings,
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -14,9 +25,21 @@ pub(super) fn plugin(app: &mut App) {
     );
 
     app.register_type::<GlobalVolumeLabel>();
+    app.register_type::<MusicVolumeLabel>();
+    app.register_type::<SoundEffectsVolumeLabel>();
+    app.register_type::<ScalingLabel>();
+    app.register_type::<TelemetryLabel>();
+    app.register_type::<LanguageLabel>();
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (
+            update_global_volume_label,
+            update_category_volume_labels,
+            update_scaling_label,
+            update_telemetry_label,
+            update_language_label,
+        )
+            .run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -26,9 +49,9 @@ fn spawn_settings_menu(mut commands: Commands) {
         GlobalZIndex(2),
         StateScoped(Menu::Settings),
         children![
-            widget::header("Settings"),
+            widget::header("@menu.settings"),
             settings_grid(),
-            widget::button("Back", go_back_on_click),
+            widget::button("@menu.back", go_back_on_click),
         ],
     ));
 }
@@ -45,26 +68,86 @@ fn settings_grid() -> impl Bundle {
         },
         children![
             (
-                widget::label("Master Volume"),
+                widget::label("@settings.master_volume"),
                 Node {
                     justify_self: JustifySelf::End,
                     ..default()
                 }
             ),
-            global_volume_widget(),
+            volume_widget(
+                "Global Volume Widget",
+                lower_global_volume,
+                raise_global_volume,
+                GlobalVolumeLabel,
+            ),
+            (
+                widget::label("@settings.music_volume"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            volume_widget(
+                "Music Volume Widget",
+                lower_music_volume,
+                raise_music_volume,
+                MusicVolumeLabel,
+            ),
+            (
+                widget::label("@settings.sound_effects_volume"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            volume_widget(
+                "Sound Effects Volume Widget",
+                lower_sound_effects_volume,
+                raise_sound_effects_volume,
+                SoundEffectsVolumeLabel,
+            ),
+            (
+                widget::label("@settings.scaling"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            scaling_widget(),
+            (
+                widget::label("@settings.telemetry"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            telemetry_widget(),
+            (
+                widget::label("@settings.language"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            language_widget(),
         ],
     )
 }
 
-fn global_volume_widget() -> impl Bundle {
+fn volume_widget<M1, M2>(
+    name: &'static str,
+    lower: impl IntoObserverSystem<Pointer<Click>, (), M1>,
+    raise: impl IntoObserverSystem<Pointer<Click>, (), M2>,
+    label: impl Component,
+) -> impl Bundle {
     (
-        Name::new("Global Volume Widget"),
+        Name::new(name),
         Node {
             justify_self: JustifySelf::Start,
             ..default()
         },
         children![
-            widget::button_small("-", lower_global_volume),
+            widget::button_small("-", lower),
             (
                 Name::new("Current Volume"),
                 Node {
@@ -72,9 +155,78 @@ fn global_volume_widget() -> impl Bundle {
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                children![(widget::label(""), GlobalVolumeLabel)],
+                children![(widget::label(""), label)],
+            ),
+            widget::button_small("+", raise),
+        ],
+    )
+}
+
+fn scaling_widget() -> impl Bundle {
+    (
+        Name::new("Scaling Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_scaling_mode),
+            (
+                Name::new("Current Scaling"),
+                Node {
+                    width: Px(120.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), ScalingLabel)],
+            ),
+            widget::button_small(">", next_scaling_mode),
+        ],
+    )
+}
+
+fn telemetry_widget() -> impl Bundle {
+    (
+        Name::new("Telemetry Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", toggle_telemetry),
+            (
+                Name::new("Current Telemetry"),
+                Node {
+                    width: Px(120.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), TelemetryLabel)],
+            ),
+            widget::button_small(">", toggle_telemetry),
+        ],
+    )
+}
+
+fn language_widget() -> impl Bundle {
+    (
+        Name::new("Language Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("<", previous_language),
+            (
+                Name::new("Current Language"),
+                Node {
+                    width: Px(120.0),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), LanguageLabel)],
             ),
-            widget::button_small("+", raise_global_volume),
+            widget::button_small(">", next_language),
         ],
     )
 }
@@ -104,6 +256,102 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+/// Category volumes go up to 100%, louder audio is set with the master volume.
+const MAX_CATEGORY_VOLUME: f32 = 1.0;
+
+fn lower_music_volume(_: Trigger<Pointer<Click>>, mut volumes: ResMut<CategoryVolumes>) {
+    volumes.music = (volumes.music - 0.1).max(MIN_VOLUME);
+}
+
+fn raise_music_volume(_: Trigger<Pointer<Click>>, mut volumes: ResMut<CategoryVolumes>) {
+    volumes.music = (volumes.music + 0.1).min(MAX_CATEGORY_VOLUME);
+}
+
+fn lower_sound_effects_volume(_: Trigger<Pointer<Click>>, mut volumes: ResMut<CategoryVolumes>) {
+    volumes.sound_effects = (volumes.sound_effects - 0.1).max(MIN_VOLUME);
+}
+
+fn raise_sound_effects_volume(_: Trigger<Pointer<Click>>, mut volumes: ResMut<CategoryVolumes>) {
+    volumes.sound_effects = (volumes.sound_effects + 0.1).min(MAX_CATEGORY_VOLUME);
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct MusicVolumeLabel;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SoundEffectsVolumeLabel;
+
+fn update_category_volume_labels(
+    volumes: Res<CategoryVolumes>,
+    mut music_label: Single<&mut Text, (With<MusicVolumeLabel>, Without<SoundEffectsVolumeLabel>)>,
+    mut sound_effects_label: Single<&mut Text, With<SoundEffectsVolumeLabel>>,
+) {
+    music_label.0 = format!("{:3.0}%", 100.0 * volumes.music);
+    sound_effects_label.0 = format!("{:3.0}%", 100.0 * volumes.sound_effects);
+}
+
+fn previous_scaling_mode(_: Trigger<Pointer<Click>>, mut scaling: ResMut<PlayfieldScaling>) {
+    *scaling = scaling.previous();
+}
+
+fn next_scaling_mode(_: Trigger<Pointer<Click>>, mut scaling: ResMut<PlayfieldScaling>) {
+    *scaling = scaling.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ScalingLabel;
+
+fn update_scaling_label(
+    scaling: Res<PlayfieldScaling>,
+    mut label: Single<&mut LocalizedText, With<ScalingLabel>>,
+) {
+    let key = format!("@settings.scaling.{}", scaling.name().to_lowercase());
+    label.set_if_neq(LocalizedText::new(key));
+}
+
+fn toggle_telemetry(_: Trigger<Pointer<Click>>, mut telemetry: ResMut<TelemetrySettings>) {
+    telemetry.enabled = !telemetry.enabled;
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TelemetryLabel;
+
+fn update_telemetry_label(
+    telemetry: Res<TelemetrySettings>,
+    mut label: Single<&mut LocalizedText, With<TelemetryLabel>>,
+) {
+    let key = if telemetry.enabled {
+        "@settings.on"
+    } else {
+        "@settings.off"
+    };
+    label.set_if_neq(LocalizedText::new(key));
+}
+
+fn previous_language(_: Trigger<Pointer<Click>>, mut language: ResMut<Language>) {
+    *language = language.previous();
+}
+
+fn next_language(_: Trigger<Pointer<Click>>, mut language: ResMut<Language>) {
+    *language = language.next();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct LanguageLabel;
+
+fn update_language_label(
+    localization: Localization,
+    mut label: Single<&mut LocalizedText, With<LanguageLabel>>,
+) {
+    let name = localization.language_name(localization.language());
+    label.set_if_neq(LocalizedText::new(name));
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -0,0 +1,143 @@
+//! A fixed logical playfield. The camera scales it to the window, so every player faces the same
+//! geometry regardless of their monitor size.
+
+use bevy::{
+    prelude::*,
+    render::camera::ScalingMode,
+    window::{PrimaryWindow, WindowResized},
+};
+
+use crate::screens::Screen;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PlayfieldScaling>();
+    app.add_systems(
+        Update,
+        apply_scaling_mode
+            .run_if(resource_changed::<PlayfieldScaling>.or(on_event::<WindowResized>)),
+    );
+
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (spawn_playfield_background, show_letterbox),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), hide_letterbox);
+}
+
+/// Size of the playfield in world units.
+pub const PLAYFIELD_SIZE: Vec2 = Vec2::new(1280.0, 720.0);
+
+const PLAYFIELD_COLOR: Color = Color::srgb(0.157, 0.157, 0.157);
+/// Behind everything else, with its border just behind it, but within the camera's clip range
+/// of -1000 to 1000.
+pub const PLAYFIELD_Z: f32 = -998.0;
+const LETTERBOX_COLOR: Color = Color::srgb(0.07, 0.07, 0.07);
+const BORDER_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+const BORDER_SIZE: f32 = 4.0;
+
+/// How the playfield is scaled to the window.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlayfieldScaling {
+    /// Show the whole playfield, letterboxing the rest of the window.
+    #[default]
+    Fit,
+    /// Cover the whole window, cropping the edges of the playfield.
+    Fill,
+    /// Like [`PlayfieldScaling::Fit`], but only scale by whole numbers for crisp pixels.
+    IntegerScale,
+}
+
+impl PlayfieldScaling {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Fit => Self::Fill,
+            Self::Fill => Self::IntegerScale,
+            Self::IntegerScale => Self::Fit,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        self.next().next()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Fit => "Fit",
+            Self::Fill => "Fill",
+            Self::IntegerScale => "Integer",
+        }
+    }
+
+    /// Projection which maps the playfield to a window of the given size.
+    pub fn projection(self, window_size: Vec2) -> Projection {
+        let (scaling_mode, scale) = match self {
+            Self::Fit => (
+                ScalingMode::AutoMin {
+                    min_width: PLAYFIELD_SIZE.x,
+                    min_height: PLAYFIELD_SIZE.y,
+                },
+                1.0,
+            ),
+            Self::Fill => (
+                ScalingMode::AutoMax {
+                    max_width: PLAYFIELD_SIZE.x,
+                    max_height: PLAYFIELD_SIZE.y,
+                },
+                1.0,
+            ),
+            Self::IntegerScale => {
+                let factor = (window_size / PLAYFIELD_SIZE)
+                    .min_element()
+                    .floor()
+                    .max(1.0);
+                (ScalingMode::WindowSize, 1.0 / factor)
+            }
+        };
+
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode,
+            scale,
+            ..OrthographicProjection::default_2d()
+        })
+    }
+}
+
+fn apply_scaling_mode(
+    scaling: Res<PlayfieldScaling>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut projection: Single<&mut Projection, With<Camera2d>>,
+) {
+    **projection = scaling.projection(window.size());
+}
+
+fn spawn_playfield_background(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn((
+        Name::new("Playfield"),
+        Mesh2d(meshes.add(Rectangle::from_size(PLAYFIELD_SIZE))),
+        MeshMaterial2d(materials.add(PLAYFIELD_COLOR)),
+        Transform::from_xyz(0.0, 0.0, PLAYFIELD_Z),
+        StateScoped(Screen::Gameplay),
+        Pickable::IGNORE,
+        children![(
+            Name::new("Playfield border"),
+            Mesh2d(meshes.add(Rectangle::from_size(
+                PLAYFIELD_SIZE + Vec2::splat(2.0 * BORDER_SIZE)
+            ))),
+            MeshMaterial2d(materials.add(BORDER_COLOR)),
+            Transform::from_xyz(0.0, 0.0, -1.0),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn show_letterbox(mut clear_color: ResMut<ClearColor>) {
+    clear_color.0 = LETTERBOX_COLOR;
+}
+
+fn hide_letterbox(mut clear_color: ResMut<ClearColor>) {
+    clear_color.0 = PLAYFIELD_COLOR;
+}
@@ -1,9 +1,29 @@
-use bevy::prelude::*;
+use std::collections::HashMap;
 
-use crate::{game::game_sequencer::GameMechanic, screens::Screen, theme::widget};
+use bevy::{
+    asset::{AssetLoader, AsyncReadExt, LoadContext, io::Reader},
+    prelude::*,
+};
+use itertools::Itertools;
+use strum_macros::EnumString;
+use thiserror::Error;
+
+use crate::{
+    asset_tracking::LoadResource,
+    game::game_sequencer::{GameMechanic, LastCheckpoint, RestartFromCheckpoint},
+    localization::LocalizedText,
+    screens::Screen,
+    theme::{
+        markup::{self, MarkupError},
+        widget,
+    },
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<GameOverData>()
+        .init_asset::<GameOverMessages>()
+        .init_asset_loader::<GameOverMessagesLoader>()
+        .load_resource::<GameOverMessageAssets>()
         .add_systems(OnEnter(Screen::GameOver), spawn_entities);
 }
 
@@ -12,37 +32,219 @@ pub struct GameOverData {
     pub reason: GameMechanic,
 }
 
-fn spawn_entities(mut commands: Commands, game_over_data: Res<GameOverData>) {
-    let title = match game_over_data.reason {
-        GameMechanic::Victory => "VICTORY",
-        _ => "GAME OVER",
-    };
+/// Cause used for game mechanics with no messages of their own.
+const DEFAULT_CAUSE: &str = "default";
+
+#[derive(Clone, Copy, EnumString)]
+#[strum(ascii_case_insensitive)]
+#[strum(serialize_all = "lowercase")]
+enum MessageField {
+    Title,
+    Message,
+    Tip,
+}
+
+#[derive(Default)]
+struct GameOverMessage {
+    title: Option<String>,
+    message: Option<String>,
+    tips: Vec<String>,
+}
+
+/// Game over titles, messages and tips per cause, loaded from a `.msg` file.
+#[derive(Asset, TypePath)]
+struct GameOverMessages {
+    causes: HashMap<GameMechanic, GameOverMessage>,
+    default: GameOverMessage,
+}
+
+impl GameOverMessages {
+    fn title(&self, cause: GameMechanic) -> &str {
+        self.causes
+            .get(&cause)
+            .and_then(|message| message.title.as_deref())
+            .or(self.default.title.as_deref())
+            .unwrap_or_default()
+    }
+
+    fn message(&self, cause: GameMechanic) -> &str {
+        self.causes
+            .get(&cause)
+            .and_then(|message| message.message.as_deref())
+            .or(self.default.message.as_deref())
+            .unwrap_or_default()
+    }
+
+    fn tips(&self, cause: GameMechanic) -> &[String] {
+        match self.causes.get(&cause) {
+            Some(message) if !message.tips.is_empty() => &message.tips,
+            _ => &self.default.tips,
+        }
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct GameOverMessageAssets {
+    #[dependency]
+    messages: Handle<GameOverMessages>,
+}
+
+impl FromWorld for GameOverMessageAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            messages: assets.load("game_over.msg"),
+        }
+    }
+}
 
-    let reason = match game_over_data.reason {
-        GameMechanic::ButtonTime => "THE BUTTON was not clicked during the last 6 seconds.",
-        GameMechanic::Durability => "THE BUTTON durability reached zero.",
-        GameMechanic::Triangles => "THE BUTTON was destroyed by triangle.",
-        GameMechanic::Pentagon => "You were caught by pentagon.",
-        GameMechanic::Victory => "CG. You managed to survive the chaos.",
-        _ => panic!("Died to unsupported game mechanic."),
+#[derive(Default)]
+struct GameOverMessagesLoader;
+
+#[derive(Error, Debug)]
+enum GameOverMessagesLoaderError {
+    #[error("Could not load asset: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Line {0}: expected `cause | field | text`.")]
+    InvalidFormat(usize),
+    #[error("Line {0}: unknown cause `{1}`.")]
+    InvalidCause(usize, String),
+    #[error("Line {0}: unknown field `{1}`, expected `title`, `message` or `tip`.")]
+    InvalidField(usize, String),
+    #[error("Line {0}: {1}.")]
+    InvalidMarkup(usize, MarkupError),
+    #[error("The `default` cause needs both a title and a message.")]
+    MissingDefault,
+}
+
+impl AssetLoader for GameOverMessagesLoader {
+    type Asset = GameOverMessages;
+    type Settings = ();
+    type Error = GameOverMessagesLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).await?;
+
+        let mut messages = GameOverMessages {
+            causes: HashMap::new(),
+            default: GameOverMessage::default(),
+        };
+
+        for (line, l) in text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !(l.is_empty() || l.starts_with("#")))
+        {
+            let (cause, field, text) = l
+                .splitn(3, '|')
+                .map(|t| t.trim())
+                .collect_tuple()
+                .ok_or(GameOverMessagesLoaderError::InvalidFormat(line))?;
+
+            let message = if cause.eq_ignore_ascii_case(DEFAULT_CAUSE) {
+                &mut messages.default
+            } else {
+                let cause = cause
+                    .parse()
+                    .map_err(|_| GameOverMessagesLoaderError::InvalidCause(line, cause.into()))?;
+                messages.causes.entry(cause).or_default()
+            };
+
+            let field = field
+                .parse()
+                .map_err(|_| GameOverMessagesLoaderError::InvalidField(line, field.into()))?;
+            if let MessageField::Message | MessageField::Tip = field {
+                markup::parse(text)
+                    .map_err(|error| GameOverMessagesLoaderError::InvalidMarkup(line, error))?;
+            }
+            match field {
+                MessageField::Title => message.title = Some(text.into()),
+                MessageField::Message => message.message = Some(text.into()),
+                MessageField::Tip => message.tips.push(text.into()),
+            }
+        }
+
+        if messages.default.title.is_none() || messages.default.message.is_none() {
+            return Err(GameOverMessagesLoaderError::MissingDefault);
+        }
+
+        Ok(messages)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["msg"]
+    }
+}
+
+fn spawn_entities(
+    mut commands: Commands,
+    game_over_data: Res<GameOverData>,
+    last_checkpoint: Res<LastCheckpoint>,
+    message_assets: Res<GameOverMessageAssets>,
+    messages: Res<Assets<GameOverMessages>>,
+    // Index of the next tip to show for each cause.
+    mut next_tips: Local<HashMap<GameMechanic, usize>>,
+) {
+    let cause = game_over_data.reason;
+    // `GameOverMessageAssets` only exists once the file has loaded, a missing or malformed file
+    // keeps the game on the loading screen and the loader reports why.
+    let Some(messages) = messages.get(&message_assets.messages) else {
+        return;
     };
 
-    commands.spawn((
-        widget::ui_root("Game over UI canvas"),
-        StateScoped(Screen::GameOver),
-        children![
-            widget::header(title),
-            widget::label(reason),
-            widget::button("Retry", on_retry_click),
-            widget::button("Exit", on_exit_click),
-        ],
-    ));
+    let tips = messages.tips(cause);
+    let tip = (!tips.is_empty()).then(|| {
+        let next_tip = next_tips.entry(cause).or_default();
+        let tip = &tips[*next_tip % tips.len()];
+        *next_tip += 1;
+        tip
+    });
+
+    commands
+        .spawn((
+            widget::ui_root("Game over UI canvas"),
+            StateScoped(Screen::GameOver),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header(messages.title(cause)));
+            parent.spawn(widget::label(messages.message(cause)));
+            if let Some(tip) = tip {
+                parent.spawn(widget::label(
+                    LocalizedText::new("@game_over.tip").with_arg("tip", tip),
+                ));
+            }
+            parent.spawn(widget::button("@game_over.retry", on_retry_click));
+            if let Some(checkpoint) = last_checkpoint
+                .0
+                .as_ref()
+                .filter(|_| game_over_data.reason != GameMechanic::Victory)
+            {
+                parent.spawn(widget::button(
+                    LocalizedText::new("@game_over.retry_from")
+                        .with_arg("checkpoint", &checkpoint.name),
+                    on_retry_from_checkpoint_click,
+                ));
+            }
+            parent.spawn(widget::button("@menu.exit", on_exit_click));
+        });
 }
 
 fn on_retry_click(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Gameplay);
 }
 
+fn on_retry_from_checkpoint_click(_: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(RestartFromCheckpoint);
+}
+
 fn on_exit_click(_: Trigger<Pointer<Click>>, mut app_exit_ew: EventWriter<AppExit>) {
     app_exit_ew.write(AppExit::Success);
 }
@@ -18,6 +18,7 @@ fn spawn_loading_screen(mut commands: Commands) {
     commands.spawn((
         widget::ui_root("Loading Screen"),
         StateScoped(Screen::Loading),
+        // Not a localization key, the string tables might still be loading.
         children![widget::label("Loading...")],
     ));
 }
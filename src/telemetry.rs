@@ -0,0 +1,347 @@
+//! Opt-in telemetry. Each run is recorded as timestamped events into its own JSONL file
+//! in the [`TELEMETRY_DIR`] folder, one JSON object per line. The `telemetry_summary` binary
+//! aggregates a folder of these logs.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, LineWriter, Write},
+};
+
+use bevy::{prelude::*, state::state::StateTransitionEvent};
+
+use crate::{
+    game::{
+        GameOver,
+        bar::{Bar, CrossingDirection, OnBarEmpty, OnBarThreshold},
+        death_freeze::DeathFreeze,
+        game_sequencer::GameMechanic,
+        guide::GuideMessage,
+        player::ClickCatcher,
+    },
+    menus::Menu,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<TelemetrySettings>()
+        .add_systems(OnEnter(Screen::Gameplay), start_run_log)
+        .add_systems(
+            Update,
+            (
+                record_transitions::<Menu>,
+                record_transitions::<GameMechanic>,
+                forget_refilled_bars,
+                // Closes the log once the run is over, so it goes last.
+                record_screen_transitions,
+            )
+                .chain()
+                .run_if(resource_exists::<TelemetryLog>),
+        )
+        .add_observer(record_guide_message)
+        .add_observer(record_click)
+        .add_observer(record_bar_threshold)
+        .add_observer(record_bar_empty)
+        .add_observer(record_game_over);
+}
+
+pub const TELEMETRY_DIR: &str = "telemetry";
+
+#[derive(Resource, Default)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+}
+
+/// The log of the current run. Only exists while a run is being recorded.
+#[derive(Resource)]
+#[cfg_attr(target_family = "wasm", allow(dead_code))]
+struct TelemetryLog {
+    writer: LineWriter<File>,
+    /// Real time at which the run started.
+    start: f32,
+    /// Bars recorded as empty, they are recorded again once they have refilled.
+    empty_bars: HashSet<Entity>,
+}
+
+enum Value<'a> {
+    Text(&'a str),
+    Number(f32),
+    Flag(bool),
+}
+
+impl TelemetryLog {
+    #[cfg(not(target_family = "wasm"))]
+    fn create(start: f32) -> io::Result<Self> {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        std::fs::create_dir_all(TELEMETRY_DIR)?;
+        let file = File::create(format!("{TELEMETRY_DIR}/run-{timestamp}.jsonl"))?;
+
+        Ok(Self {
+            writer: LineWriter::new(file),
+            start,
+            empty_bars: HashSet::new(),
+        })
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn create(_start: f32) -> io::Result<Self> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+
+    fn record(&mut self, now: f32, event: &str, fields: &[(&str, Value)]) {
+        let mut line = format!(
+            r#"{{"t":{:.3},"event":"{}""#,
+            now - self.start,
+            escape(event)
+        );
+        for (key, value) in fields {
+            line += &format!(r#","{key}":"#);
+            match value {
+                Value::Text(text) => line += &format!(r#""{}""#, escape(text)),
+                Value::Number(number) => line += &number.to_string(),
+                Value::Flag(flag) => line += &flag.to_string(),
+            }
+        }
+        line += "}\n";
+
+        if let Err(error) = self.writer.write_all(line.as_bytes()) {
+            warn!("Could not write telemetry: {error}");
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            char if char.is_control() => escaped += &format!("\\u{:04x}", char as u32),
+            char => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+fn start_run_log(mut commands: Commands, settings: Res<TelemetrySettings>, time: Res<Time<Real>>) {
+    if !settings.enabled {
+        return;
+    }
+
+    match TelemetryLog::create(time.elapsed_secs()) {
+        Ok(log) => commands.insert_resource(log),
+        Err(error) => warn!("Could not start telemetry: {error}"),
+    }
+}
+
+fn record_transitions<S: States>(
+    mut transitions: EventReader<StateTransitionEvent<S>>,
+    mut log: ResMut<TelemetryLog>,
+    time: Res<Time<Real>>,
+) {
+    for transition in transitions.read() {
+        record_transition(&mut log, time.elapsed_secs(), transition);
+    }
+}
+
+/// Records screen transitions and closes the log once the run has left gameplay.
+fn record_screen_transitions(
+    mut commands: Commands,
+    mut transitions: EventReader<StateTransitionEvent<Screen>>,
+    mut log: ResMut<TelemetryLog>,
+    time: Res<Time<Real>>,
+) {
+    for transition in transitions.read() {
+        record_transition(&mut log, time.elapsed_secs(), transition);
+
+        if transition.exited == Some(Screen::Gameplay) {
+            commands.remove_resource::<TelemetryLog>();
+        }
+    }
+}
+
+fn record_transition<S: States>(
+    log: &mut TelemetryLog,
+    now: f32,
+    transition: &StateTransitionEvent<S>,
+) {
+    let (Some(exited), Some(entered)) = (&transition.exited, &transition.entered) else {
+        return;
+    };
+    let state = std::any::type_name::<S>()
+        .rsplit("::")
+        .next()
+        .unwrap_or_default();
+
+    log.record(
+        now,
+        "transition",
+        &[
+            ("state", Value::Text(state)),
+            ("from", Value::Text(&format!("{exited:?}"))),
+            ("to", Value::Text(&format!("{entered:?}"))),
+        ],
+    );
+}
+
+fn record_guide_message(
+    trigger: Trigger<GuideMessage>,
+    log: Option<ResMut<TelemetryLog>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(mut log) = log else {
+        return;
+    };
+
+    let message = trigger.event();
+    log.record(
+        time.elapsed_secs(),
+        "guide_text",
+        &[
+            ("text", Value::Text(&message.text)),
+            ("priority", Value::Text(&format!("{:?}", message.priority))),
+        ],
+    );
+}
+
+fn record_click(
+    trigger: Trigger<Pointer<Click>>,
+    targets: Query<(Has<ClickCatcher>, Option<&Name>)>,
+    log: Option<ResMut<TelemetryLog>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(mut log) = log else {
+        return;
+    };
+    // Clicks bubble up the hierarchy, only record them once.
+    if trigger.target() != trigger.event().target {
+        return;
+    }
+
+    let Ok((missed, name)) = targets.get(trigger.target()) else {
+        return;
+    };
+    let position = trigger
+        .hit
+        .position
+        .map_or(trigger.pointer_location.position, |position| position.xy());
+
+    let mut fields = vec![
+        ("x", Value::Number(position.x)),
+        ("y", Value::Number(position.y)),
+        ("hit", Value::Flag(!missed)),
+    ];
+    if let Some(name) = name {
+        fields.push(("target", Value::Text(name.as_str())));
+    }
+    log.record(time.elapsed_secs(), "click", &fields);
+}
+
+fn record_bar_threshold(
+    trigger: Trigger<OnBarThreshold>,
+    names: Query<&Name>,
+    log: Option<ResMut<TelemetryLog>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(mut log) = log else {
+        return;
+    };
+
+    let event = trigger.event();
+    let direction = match event.crossing_direction {
+        CrossingDirection::Falling => "falling",
+        CrossingDirection::Rising => "rising",
+    };
+    log.record(
+        time.elapsed_secs(),
+        "bar_threshold",
+        &[
+            ("bar", Value::Text(bar_name(&names, event.sender))),
+            ("threshold", Value::Number(event.threshold)),
+            ("direction", Value::Text(direction)),
+        ],
+    );
+}
+
+fn record_bar_empty(
+    trigger: Trigger<OnBarEmpty>,
+    names: Query<&Name>,
+    log: Option<ResMut<TelemetryLog>>,
+    time: Res<Time<Real>>,
+) {
+    let Some(mut log) = log else {
+        return;
+    };
+    // Empty bars keep triggering while they change, only record them becoming empty.
+    let bar = trigger.event().sender;
+    if !log.empty_bars.insert(bar) {
+        return;
+    }
+
+    log.record(
+        time.elapsed_secs(),
+        "bar_empty",
+        &[("bar", Value::Text(bar_name(&names, bar)))],
+    );
+}
+
+fn forget_refilled_bars(mut log: ResMut<TelemetryLog>, bars: Query<&Bar>) {
+    log.empty_bars
+        .retain(|&bar| bars.get(bar).is_ok_and(|bar| bar.current == bar.min));
+}
+
+fn bar_name<'a>(names: &'a Query<&Name>, bar: Entity) -> &'a str {
+    names.get(bar).map_or("unnamed", Name::as_str)
+}
+
+fn record_game_over(
+    trigger: Trigger<GameOver>,
+    log: Option<ResMut<TelemetryLog>>,
+    freeze: Option<Res<DeathFreeze>>,
+    #[cfg(feature = "dev")] god_mode: Res<crate::game::god_mode::GodMode>,
+    time: Res<Time<Real>>,
+) {
+    let Some(mut log) = log else {
+        return;
+    };
+    let event = trigger.event();
+    // Only the game over which ends the run, not the repeats while frozen or those god mode absorbs.
+    if freeze.is_some() {
+        return;
+    }
+    #[cfg(feature = "dev")]
+    if god_mode.enabled && event.cause != GameMechanic::Victory {
+        return;
+    }
+
+    let reason = format!("{:?}", event.cause);
+    let mut fields = vec![("reason", Value::Text(&reason))];
+    if let Some(elapsed_time) = event.elapsed_time {
+        fields.push(("elapsed_time", Value::Number(elapsed_time)));
+    }
+    if let Some(position) = event.position {
+        fields.push(("x", Value::Number(position.x)));
+        fields.push(("y", Value::Number(position.y)));
+    }
+
+    // Each bar is recorded as `<bar name>` with its value and `<bar name>_progress` with 0..1.
+    let bar_keys: Vec<_> = event
+        .bars
+        .iter()
+        .map(|bar| {
+            let key = bar.name.to_lowercase().replace(' ', "_");
+            (key.clone(), format!("{key}_progress"))
+        })
+        .collect();
+    for (bar, (key, progress_key)) in event.bars.iter().zip(&bar_keys) {
+        fields.push((key, Value::Number(bar.current)));
+        fields.push((progress_key, Value::Number(bar.progress)));
+    }
+
+    log.record(time.elapsed_secs(), "game_over", &fields);
+}
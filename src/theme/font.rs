@@ -0,0 +1,138 @@
+//! Fonts. Texts use the primary font of the [`FontRegistry`], text spans with characters it
+//! doesn't have fall back to the first font of the list which has all of them.
+
+use std::collections::HashSet;
+
+use bevy::{prelude::*, text::Update2dText, ui::UiSystem};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FontRegistry>().add_systems(
+        PostUpdate,
+        assign_fonts.before(UiSystem::Prepare).before(Update2dText),
+    );
+}
+
+const PRIMARY_FONT: &str = "fonts/Super Vanilla.ttf";
+/// Fonts for characters missing in the primary font, in order of preference. DejaVu Sans
+/// covers Latin, Greek and Cyrillic, a CJK font would go after it.
+const FALLBACK_FONTS: [&str; 1] = ["fonts/DejaVuSans.ttf"];
+
+#[derive(Resource)]
+pub struct FontRegistry {
+    /// The primary font followed by the fallbacks.
+    fonts: Vec<Handle<Font>>,
+    /// Characters each font has glyphs for, read once it has loaded.
+    coverage: Vec<Option<HashSet<char>>>,
+}
+
+impl FromWorld for FontRegistry {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            fonts: std::iter::once(PRIMARY_FONT)
+                .chain(FALLBACK_FONTS)
+                .map(|path| assets.load(path))
+                .collect(),
+            coverage: vec![None; 1 + FALLBACK_FONTS.len()],
+        }
+    }
+}
+
+impl FontRegistry {
+    pub fn primary(&self) -> Handle<Font> {
+        self.fonts[0].clone()
+    }
+
+    /// Texts with Bevy's default font or one of the registry's fonts get their font from the
+    /// registry, texts with any other font are left alone.
+    fn manages(&self, font: &Handle<Font>) -> bool {
+        *font == Handle::default() || self.fonts.contains(font)
+    }
+
+    /// The first font which has every character of the text, or the one with the fewest missing
+    /// characters if there is no such font.
+    fn font_for(&self, text: &str) -> Handle<Font> {
+        let chars: Vec<char> = text.chars().filter(|char| !char.is_whitespace()).collect();
+        if chars.is_empty() {
+            return self.primary();
+        }
+
+        self.fonts
+            .iter()
+            .zip(&self.coverage)
+            .min_by_key(|(_, coverage)| missing_glyphs(coverage.as_ref(), &chars))
+            .map(|(handle, _)| handle.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Characters with a glyph in the font, none if it can't be parsed.
+fn coverage(font: &Font) -> HashSet<char> {
+    let Ok(face) = ttf_parser::Face::parse(&font.data, 0) else {
+        return HashSet::new();
+    };
+    let mut chars = HashSet::new();
+    for subtable in face.tables().cmap.iter().flat_map(|cmap| cmap.subtables) {
+        if subtable.is_unicode() {
+            subtable.codepoints(|codepoint| chars.extend(char::from_u32(codepoint)));
+        }
+    }
+    chars.retain(|char| face.glyph_index(*char).is_some());
+    chars
+}
+
+/// Number of characters without a glyph in the font. Fonts which aren't loaded yet have none.
+fn missing_glyphs(coverage: Option<&HashSet<char>>, chars: &[char]) -> usize {
+    let Some(coverage) = coverage else {
+        return chars.len();
+    };
+    chars.iter().filter(|char| !coverage.contains(char)).count()
+}
+
+fn assign_fonts(
+    mut registry: ResMut<FontRegistry>,
+    fonts: Res<Assets<Font>>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+    mut texts: Query<(
+        &mut TextFont,
+        Option<Ref<Text>>,
+        Option<Ref<TextSpan>>,
+        Option<Ref<Text2d>>,
+    )>,
+) {
+    // Fonts are chosen again once they are loaded, as coverage is only known from then on.
+    let mut fonts_loaded = false;
+    for event in font_events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = event else {
+            continue;
+        };
+        fonts_loaded = true;
+        let index = registry.fonts.iter().position(|handle| handle.id() == *id);
+        if let Some((index, font)) = index.zip(fonts.get(*id)) {
+            registry.coverage[index] = Some(coverage(font));
+        }
+    }
+
+    for (mut font, text, span, text_2d) in &mut texts {
+        let Some((content, changed)) = text
+            .as_ref()
+            .map(|text| (text.0.as_str(), text.is_changed()))
+            .or(span
+                .as_ref()
+                .map(|span| (span.0.as_str(), span.is_changed())))
+            .or(text_2d
+                .as_ref()
+                .map(|text| (text.0.as_str(), text.is_changed())))
+        else {
+            continue;
+        };
+        if !(changed || fonts_loaded) || !registry.manages(&font.font) {
+            continue;
+        }
+
+        let chosen = registry.font_for(content);
+        if font.font != chosen {
+            font.font = chosen;
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! A small markup language for rich text, e.g. `Click [color=green]THE BUTTON[/color]!`.
+//!
+//! Supported tags:
+//! - `[color=<name or #hex>]`: text color
+//! - `[b]`: bold, rendered slightly larger and brighter, as the game ships a single font weight
+//! - `[size=<px>]`: font size
+//! - `[shake]`, `[wave]`: animated letters
+//!
+//! Tags must be closed in reverse order, e.g. `[b][shake]RUN![/shake][/b]`. Use `[[` for a
+//! literal `[`.
+
+use std::f32::consts::TAU;
+
+use bevy::{color::palettes::css, prelude::*};
+use rand::Rng;
+use thiserror::Error;
+
+use crate::localization::update_localized_texts;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (render_markup, animate_spans)
+            .chain()
+            .after(update_localized_texts),
+    );
+}
+
+/// How much bigger bold text is.
+const BOLD_SCALE: f32 = 1.15;
+/// Relative font size change of animated letters.
+const SHAKE_AMPLITUDE: f32 = 0.15;
+const WAVE_AMPLITUDE: f32 = 0.2;
+/// Waves per second.
+const WAVE_SPEED: f32 = 1.5;
+/// Phase difference between two neighbouring letters of a wave, in waves.
+const WAVE_LETTER_OFFSET: f32 = 0.1;
+/// Font size changes of animated letters are snapped to this many steps each way. Every font
+/// size gets its own font atlas, so the sizes have to be few.
+const ANIMATION_STEPS: f32 = 2.0;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MarkupError {
+    #[error("unknown tag `[{0}]`")]
+    UnknownTag(String),
+    #[error("invalid value `{1}` of tag `[{0}]`")]
+    InvalidValue(String, String),
+    #[error("`[/{0}]` doesn't close an open tag")]
+    UnexpectedClosingTag(String),
+    #[error("tag `[{0}]` is never closed")]
+    UnclosedTag(String),
+    #[error("missing `]`")]
+    UnterminatedTag,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpanEffect {
+    Shake,
+    Wave,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SpanStyle {
+    pub color: Option<Color>,
+    pub bold: bool,
+    pub size: Option<f32>,
+    pub effect: Option<SpanEffect>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkupSpan {
+    pub text: String,
+    pub style: SpanStyle,
+}
+
+/// Parses markup into spans of text with the same style.
+pub fn parse(source: &str) -> Result<Vec<MarkupSpan>, MarkupError> {
+    let mut spans = Vec::new();
+    // open tags with the style from before they were opened
+    let mut open_tags: Vec<(&str, SpanStyle)> = Vec::new();
+    let mut style = SpanStyle::default();
+    let mut text = String::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find('[') {
+        text += &rest[..start];
+        rest = &rest[start + 1..];
+
+        if let Some(after_escape) = rest.strip_prefix('[') {
+            text.push('[');
+            rest = after_escape;
+            continue;
+        }
+
+        let end = rest.find(']').ok_or(MarkupError::UnterminatedTag)?;
+        let tag = rest[..end].trim();
+        rest = &rest[end + 1..];
+
+        if !text.is_empty() {
+            spans.push(MarkupSpan {
+                text: std::mem::take(&mut text),
+                style,
+            });
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            match open_tags.pop() {
+                Some((open_name, previous_style)) if open_name == name.trim() => {
+                    style = previous_style;
+                }
+                _ => return Err(MarkupError::UnexpectedClosingTag(name.trim().into())),
+            }
+            continue;
+        }
+
+        let (name, value) = tag.split_once('=').map_or((tag, None), |(name, value)| {
+            (name.trim(), Some(value.trim()))
+        });
+        let invalid_value = || MarkupError::InvalidValue(name.into(), value.unwrap_or("").into());
+
+        open_tags.push((name, style));
+        match (name, value) {
+            ("color", Some(value)) => {
+                style.color = Some(parse_color(value).ok_or_else(invalid_value)?)
+            }
+            ("size", Some(value)) => {
+                style.size = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|size: &f32| *size > 0.0)
+                        .ok_or_else(invalid_value)?,
+                );
+            }
+            ("b", None) => style.bold = true,
+            ("shake", None) => style.effect = Some(SpanEffect::Shake),
+            ("wave", None) => style.effect = Some(SpanEffect::Wave),
+            ("color" | "size", None) | ("b" | "shake" | "wave", Some(_)) => {
+                return Err(invalid_value());
+            }
+            _ => return Err(MarkupError::UnknownTag(tag.into())),
+        }
+    }
+    text += rest;
+
+    if let Some((name, _)) = open_tags.pop() {
+        return Err(MarkupError::UnclosedTag(name.into()));
+    }
+    if !text.is_empty() {
+        spans.push(MarkupSpan { text, style });
+    }
+
+    Ok(spans)
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+    if value.starts_with('#') {
+        return Srgba::hex(value).ok().map(Color::from);
+    }
+
+    let color = match value.to_ascii_lowercase().as_str() {
+        "white" => css::WHITE,
+        "gray" | "grey" => css::GRAY,
+        "red" => css::RED,
+        "green" => css::LIME,
+        "blue" => css::BLUE,
+        "yellow" => css::YELLOW,
+        "orange" => css::ORANGE,
+        "purple" => css::PURPLE,
+        "magenta" => css::MAGENTA,
+        _ => return None,
+    };
+    Some(color.into())
+}
+
+/// Text written in markup. Rendered as [`TextSpan`] children of the entity's [`Text`], which
+/// provides the default font, size and color through its [`TextFont`] and [`TextColor`].
+#[derive(Component, Default)]
+#[require(Text)]
+pub struct Markup {
+    spans: Vec<MarkupSpan>,
+    /// Number of characters shown, all of them if `None`.
+    revealed: Option<usize>,
+}
+
+impl Markup {
+    /// Parses the markup, showing it as plain text if it is invalid.
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        let spans = parse(&source).unwrap_or_else(|error| {
+            warn!("Invalid markup `{source}`: {error}");
+            vec![MarkupSpan {
+                text: source,
+                style: SpanStyle::default(),
+            }]
+        });
+
+        Self {
+            spans,
+            revealed: None,
+        }
+    }
+
+    /// The text without any markup.
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    /// Shows only the first `characters` characters, e.g. for a typewriter effect.
+    pub fn reveal(&mut self, characters: Option<usize>) {
+        self.revealed = characters;
+    }
+}
+
+/// Span spawned from [`Markup`].
+#[derive(Component)]
+struct MarkupSpanEntity;
+
+#[derive(Component)]
+struct AnimatedSpan {
+    effect: SpanEffect,
+    base_size: f32,
+    /// Position of the letter, used to offset the animation.
+    index: usize,
+}
+
+fn render_markup(
+    mut commands: Commands,
+    mut markups: Query<
+        (
+            Entity,
+            &Markup,
+            &mut Text,
+            Option<&TextFont>,
+            Option<&TextColor>,
+            Option<&Children>,
+        ),
+        Changed<Markup>,
+    >,
+    spans: Query<(), With<MarkupSpanEntity>>,
+) {
+    for (entity, markup, mut text, font, color, children) in &mut markups {
+        text.0.clear();
+        for child in children.into_iter().flatten() {
+            if spans.contains(*child) {
+                commands.entity(*child).despawn();
+            }
+        }
+
+        let font = font.cloned().unwrap_or_default();
+        let color = color.copied().unwrap_or_default();
+        let mut remaining = markup.revealed.unwrap_or(usize::MAX);
+        let mut index = 0;
+
+        for span in &markup.spans {
+            if remaining == 0 {
+                break;
+            }
+            let shown: String = span.text.chars().take(remaining).collect();
+            remaining -= shown.chars().count();
+
+            let mut span_font = font.clone();
+            span_font.font_size = span.style.size.unwrap_or(font.font_size);
+            let mut span_color = span.style.color.map_or(color, TextColor);
+            if span.style.bold {
+                span_font.font_size *= BOLD_SCALE;
+                span_color.0 = span_color.0.lighter(0.15);
+            }
+
+            let Some(effect) = span.style.effect else {
+                commands.entity(entity).with_child((
+                    TextSpan::new(shown),
+                    span_font,
+                    span_color,
+                    MarkupSpanEntity,
+                ));
+                continue;
+            };
+
+            // every letter is its own span, so letters can be animated separately
+            for letter in shown.chars() {
+                commands.entity(entity).with_child((
+                    TextSpan::new(letter),
+                    span_font.clone(),
+                    span_color,
+                    MarkupSpanEntity,
+                    AnimatedSpan {
+                        effect,
+                        base_size: span_font.font_size,
+                        index,
+                    },
+                ));
+                index += 1;
+            }
+        }
+    }
+}
+
+fn animate_spans(mut spans: Query<(&mut TextFont, &AnimatedSpan)>, time: Res<Time>) {
+    let mut rng = rand::rng();
+
+    for (mut font, span) in &mut spans {
+        let (offset, amplitude) = match span.effect {
+            SpanEffect::Shake => (rng.random_range(-1.0..=1.0), SHAKE_AMPLITUDE),
+            SpanEffect::Wave => {
+                let phase =
+                    time.elapsed_secs() * WAVE_SPEED - span.index as f32 * WAVE_LETTER_OFFSET;
+                ((phase * TAU).sin(), WAVE_AMPLITUDE)
+            }
+        };
+        let step = (offset * ANIMATION_STEPS).round() / ANIMATION_STEPS;
+        let font_size = span.base_size * (1.0 + step * amplitude);
+        // only changed sizes are laid out again
+        if font.font_size != font_size {
+            font.font_size = font_size;
+        }
+    }
+}
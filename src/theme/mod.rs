@@ -3,7 +3,9 @@
 // Unused utilities may trigger this lints undesirably.
 #![allow(dead_code)]
 
+pub mod font;
 pub mod interaction;
+pub mod markup;
 pub mod palette;
 pub mod widget;
 
@@ -15,5 +17,5 @@ pub mod prelude {
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((font::plugin, interaction::plugin, markup::plugin));
 }
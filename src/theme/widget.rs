@@ -8,7 +8,10 @@ use bevy::{
     ui::Val::*,
 };
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use crate::{
+    localization::LocalizedText,
+    theme::{interaction::InteractionPalette, markup::Markup, palette::*},
+};
 
 /// A root UI node that fills the window and centers its content.
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
@@ -29,28 +32,32 @@ pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
     )
 }
 
-/// A simple header label. Bigger than [`label`].
-pub fn header(text: impl Into<String>) -> impl Bundle {
+/// A simple header label. Bigger than [`label`]. The text can be a localization key, see
+/// [`localization`](crate::localization).
+pub fn header(text: impl Into<LocalizedText>) -> impl Bundle {
     (
         Name::new("Header"),
-        Text(text.into()),
+        text.into(),
+        Text::default(),
         TextFont::from_font_size(40.0),
         TextColor(HEADER_TEXT),
     )
 }
 
-/// A simple text label.
-pub fn label(text: impl Into<String>) -> impl Bundle {
+/// A simple text label. The text can be a localization key and can contain markup, see
+/// [`localization`](crate::localization) and [`markup`](crate::theme::markup).
+pub fn label(text: impl Into<LocalizedText>) -> impl Bundle {
     (
         Name::new("Label"),
-        Text(text.into()),
+        text.into(),
+        Markup::default(),
         TextFont::from_font_size(24.0),
         TextColor(LABEL_TEXT),
     )
 }
 
 /// A large rounded button with text and an action defined as an [`Observer`].
-pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+pub fn button<E, B, M, I>(text: impl Into<LocalizedText>, action: I) -> impl Bundle
 where
     E: Event,
     B: Bundle,
@@ -73,7 +80,7 @@ where
 }
 
 /// A small square button with text and an action defined as an [`Observer`].
-pub fn button_small<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+pub fn button_small<E, B, M, I>(text: impl Into<LocalizedText>, action: I) -> impl Bundle
 where
     E: Event,
     B: Bundle,
@@ -94,7 +101,7 @@ where
 
 /// A simple button with text and an action defined as an [`Observer`]. The button's layout is provided by `button_bundle`.
 fn button_base<E, B, M, I>(
-    text: impl Into<String>,
+    text: impl Into<LocalizedText>,
     action: I,
     button_bundle: impl Bundle,
 ) -> impl Bundle
@@ -121,7 +128,8 @@ where
                     },
                     children![(
                         Name::new("Button Text"),
-                        Text(text),
+                        text,
+                        Text::default(),
                         TextFont::from_font_size(40.0),
                         TextColor(BUTTON_TEXT),
                         // Don't bubble picking events from the text up to the button.